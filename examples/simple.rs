@@ -22,8 +22,6 @@ mod my_entity {
     impl ActiveModelBehavior for ActiveModel {}
 }
 
-use my_entity::{Column, Entity};
-
 fn main() {
     // Build a safe select and inspect params
     let (_q, params) = my_entity::DemoItemSelect::new()
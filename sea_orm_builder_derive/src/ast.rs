@@ -1,46 +1,572 @@
 //! AST parsing utilities for sea_orm_builder_derive
 //!
 //! - Parses the input DeriveInput (SeaORM Model) to a simplified shape
-//! - Extracts per-field permissions from `#[sea_builder(...)]`
-//! - Derives an entity prefix from `#[sea_orm(table_name = "...")]`
+//! - Extracts per-field permissions from `#[sea_builder(...)]`, layered on top
+//!   of the struct-level `#[sea_builder(...)]` defaults
+//! - Derives an entity prefix from `#[sea_orm(table_name = "...")]`, unless the
+//!   struct sets `name`; `vis` and `module` control where the builders go and
+//!   `entity`/`column` where they find the SeaORM types
+//! - Merges field permissions from the optional TOML manifest (see `manifest`)
 
 use heck::ToUpperCamelCase;
-use syn::{Attribute, Data, DeriveInput, Fields, LitStr};
+use proc_macro2::Span;
+use quote::format_ident;
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr, Token};
+
+use crate::manifest::{self, Manifest};
+use crate::types::{classify, support, FieldType, Support, TypeClass};
 
 /// Per-field permissions configured via `#[sea_builder(...)]`.
 #[derive(Default, Debug, Clone)]
 pub struct FieldPerms {
-    pub select_where: Vec<String>,
-    pub update_where: Vec<String>,
+    pub select_where: Vec<WhereOp>,
+    pub update_where: Vec<WhereOp>,
     pub update_set: bool,
-    pub delete_where: Vec<String>,
+    pub delete_where: Vec<WhereOp>,
+    pub select_json: Vec<JsonPath>,
+    pub update_json: Vec<JsonPath>,
+    pub delete_json: Vec<JsonPath>,
+    pub select_tuples: Vec<TupleIn>,
+    pub update_tuples: Vec<TupleIn>,
+    pub delete_tuples: Vec<TupleIn>,
+}
+
+/// Fields matched together: `where(tuple_in(tenant_id, user_id))` at struct
+/// level, for `(tenant_id, user_id) IN (..)`.
+#[derive(Debug, Clone)]
+pub struct TupleIn {
+    pub span: Span,
+    pub fields: Vec<syn::Ident>,
+}
+
+/// A key inside a JSON field: `where(json_path(source: String = [eq, in]))`.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    /// Key name without `r#`.
+    pub key: String,
+    pub span: Span,
+    pub field_type: FieldType,
+    pub ops: Vec<WhereOp>,
+}
+
+/// An op as written in an attribute; the span points at its token.
+#[derive(Copy, Clone, Debug)]
+pub struct WhereOp {
+    pub op: Op,
+    pub span: Span,
+}
+
+/// A `where(...)` op with aliases already normalised. Its `name()` is the only
+/// spelling codegen uses: method suffix, storage suffix and `WhereParam::op`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Like,
+    Ilike,
+    NotLike,
+    NotIlike,
+    Ieq,
+    Regex,
+    NotRegex,
+    Fts,
+    StartsWith,
+    EndsWith,
+    Contains,
+    IstartsWith,
+    IendsWith,
+    Icontains,
+    ContainedBy,
+    Overlaps,
+    AnyEq,
+    HasFlags,
+    HasAnyFlag,
+    LacksFlags,
+    In,
+    NotIn,
+    InSelect,
+    NotInSelect,
+    Between,
+    NotBetween,
+    On,
+    Before,
+    Since,
+    WithinLast,
+    EqCol,
+    NeCol,
+    LtCol,
+    LteCol,
+    GtCol,
+    GteCol,
+    IsNull,
+    IsNotNull,
+    EqNullSafe,
+}
+
+/// The input an op takes, which decides its method signature and storage.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OpShape {
+    /// One value: `<field>_<op>(v)`
+    Single,
+    /// Any iterator of values: `<field>_<op>([a, b])`
+    List,
+    /// Two values: `<field>_<op>(a, b)`
+    Range,
+    /// No value: `<field>_<op>()`
+    Flag,
+}
+
+impl Op {
+    pub const ALL: &'static [Op] = &[
+        Op::Eq,
+        Op::Ne,
+        Op::Lt,
+        Op::Lte,
+        Op::Gt,
+        Op::Gte,
+        Op::Like,
+        Op::Ilike,
+        Op::NotLike,
+        Op::NotIlike,
+        Op::Ieq,
+        Op::Regex,
+        Op::NotRegex,
+        Op::Fts,
+        Op::StartsWith,
+        Op::EndsWith,
+        Op::Contains,
+        Op::IstartsWith,
+        Op::IendsWith,
+        Op::Icontains,
+        Op::ContainedBy,
+        Op::Overlaps,
+        Op::AnyEq,
+        Op::HasFlags,
+        Op::HasAnyFlag,
+        Op::LacksFlags,
+        Op::In,
+        Op::NotIn,
+        Op::InSelect,
+        Op::NotInSelect,
+        Op::Between,
+        Op::NotBetween,
+        Op::On,
+        Op::Before,
+        Op::Since,
+        Op::WithinLast,
+        Op::EqCol,
+        Op::NeCol,
+        Op::LtCol,
+        Op::LteCol,
+        Op::GtCol,
+        Op::GteCol,
+        Op::IsNull,
+        Op::IsNotNull,
+        Op::EqNullSafe,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Eq => "eq",
+            Op::Ne => "ne",
+            Op::Lt => "lt",
+            Op::Lte => "lte",
+            Op::Gt => "gt",
+            Op::Gte => "gte",
+            Op::Like => "like",
+            Op::Ilike => "ilike",
+            Op::NotLike => "not_like",
+            Op::NotIlike => "not_ilike",
+            Op::Ieq => "ieq",
+            Op::Regex => "regex",
+            Op::NotRegex => "not_regex",
+            Op::Fts => "fts",
+            Op::StartsWith => "starts_with",
+            Op::EndsWith => "ends_with",
+            Op::Contains => "contains",
+            Op::IstartsWith => "istarts_with",
+            Op::IendsWith => "iends_with",
+            Op::Icontains => "icontains",
+            Op::ContainedBy => "contained_by",
+            Op::Overlaps => "overlaps",
+            Op::AnyEq => "any_eq",
+            Op::HasFlags => "has_flags",
+            Op::HasAnyFlag => "has_any_flag",
+            Op::LacksFlags => "lacks_flags",
+            Op::In => "in",
+            Op::NotIn => "not_in",
+            Op::InSelect => "in_select",
+            Op::NotInSelect => "not_in_select",
+            Op::Between => "between",
+            Op::NotBetween => "not_between",
+            Op::On => "on",
+            Op::Before => "before",
+            Op::Since => "since",
+            Op::WithinLast => "within_last",
+            Op::EqCol => "eq_col",
+            Op::NeCol => "ne_col",
+            Op::LtCol => "lt_col",
+            Op::LteCol => "lte_col",
+            Op::GtCol => "gt_col",
+            Op::GteCol => "gte_col",
+            Op::IsNull => "is_null",
+            Op::IsNotNull => "is_not_null",
+            Op::EqNullSafe => "eq_null_safe",
+        }
+    }
+
+    /// Alternative spellings accepted in attributes.
+    pub fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Op::Ne => &["neq"],
+            Op::Lte => &["le"],
+            Op::Gte => &["ge"],
+            Op::In => &["isin", "is_in"],
+            Op::NotLike => &["notlike"],
+            Op::NotIlike => &["notilike"],
+            Op::Fts => &["full_text"],
+            Op::StartsWith => &["startswith"],
+            Op::EndsWith => &["endswith"],
+            Op::IstartsWith => &["istartswith"],
+            Op::IendsWith => &["iendswith"],
+            Op::NotIn => &["notin", "is_not_in"],
+            Op::NotBetween => &["notbetween"],
+            Op::IsNull => &["isnull"],
+            Op::IsNotNull => &["isnotnull", "not_null"],
+            Op::EqNullSafe => &["is_not_distinct_from"],
+            _ => &[],
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Op> {
+        Op::ALL
+            .iter()
+            .copied()
+            .find(|op| op.name() == s || op.aliases().contains(&s))
+    }
+
+    pub fn shape(self) -> OpShape {
+        match self {
+            Op::In | Op::NotIn => OpShape::List,
+            Op::Between | Op::NotBetween => OpShape::Range,
+            Op::IsNull | Op::IsNotNull => OpShape::Flag,
+            _ => OpShape::Single,
+        }
+    }
+
+    /// Ops that only make sense on `Option<_>` fields.
+    pub fn nullable_only(self) -> bool {
+        matches!(self, Op::IsNull | Op::IsNotNull | Op::EqNullSafe)
+    }
+
+    /// The shape of ops on Postgres array (`Vec<T>`) fields, which take
+    /// elements rather than the whole field; `contains` means `@>` there.
+    pub fn array_shape(self) -> Option<OpShape> {
+        match self {
+            Op::Contains | Op::ContainedBy | Op::Overlaps => Some(OpShape::List),
+            Op::AnyEq => Some(OpShape::Single),
+            _ => None,
+        }
+    }
+
+    /// Bitmask tests on integer fields.
+    pub fn bitflag(self) -> bool {
+        matches!(self, Op::HasFlags | Op::HasAnyFlag | Op::LacksFlags)
+    }
+
+    /// Date and time conveniences on chrono `DateTimeUtc`/`Date` fields.
+    pub fn temporal(self) -> bool {
+        matches!(self, Op::On | Op::Before | Op::Since | Op::WithinLast)
+    }
+
+    /// The column-to-column form of a comparison, for `cmp_col(..)`.
+    pub fn to_col(self) -> Option<Op> {
+        match self {
+            Op::Eq => Some(Op::EqCol),
+            Op::Ne => Some(Op::NeCol),
+            Op::Lt => Some(Op::LtCol),
+            Op::Lte => Some(Op::LteCol),
+            Op::Gt => Some(Op::GtCol),
+            Op::Gte => Some(Op::GteCol),
+            _ => None,
+        }
+    }
+
+    /// The value comparison a column-to-column op is built on.
+    pub fn col_base(self) -> Option<Op> {
+        Op::ALL.iter().copied().find(|op| op.to_col() == Some(self))
+    }
+
+    /// Ops that apply to a key extracted with `json_path`.
+    pub fn json_supported(self) -> bool {
+        matches!(
+            self,
+            Op::Eq
+                | Op::Ne
+                | Op::Lt
+                | Op::Lte
+                | Op::Gt
+                | Op::Gte
+                | Op::Like
+                | Op::NotLike
+                | Op::In
+                | Op::NotIn
+                | Op::Between
+                | Op::NotBetween
+                | Op::IsNull
+                | Op::IsNotNull
+        )
+    }
+
+    /// Ops without portable SQL, generated only with a backend feature.
+    pub fn needs_backend(self) -> bool {
+        matches!(self, Op::Regex | Op::NotRegex | Op::Fts)
+    }
+}
+
+/// The backend feature forwarded from sea_orm_builder, first of postgres,
+/// mysql, sqlite; the runtime crate picks the same one.
+pub fn backend() -> Option<&'static str> {
+    if cfg!(feature = "postgres") {
+        Some("postgres")
+    } else if cfg!(feature = "mysql") {
+        Some("mysql")
+    } else if cfg!(feature = "sqlite") {
+        Some("sqlite")
+    } else {
+        None
+    }
+}
+
+/// Error for `what` (an op, `search`, ..) used without a backend feature.
+fn no_backend_err(span: Span, what: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "{} has no portable SQL; enable the `postgres`, `mysql` or `sqlite` feature of sea_orm_builder",
+            what
+        ),
+    )
+}
+
+/// A named group of ops, accepted wherever ops are listed.
+#[derive(Clone, Debug)]
+pub struct Preset {
+    pub name: String,
+    pub ops: Vec<Op>,
+}
+
+impl Preset {
+    /// Presets every model gets.
+    pub fn builtins() -> Vec<Preset> {
+        use Op::*;
+        let preset = |name: &str, ops: &[Op]| Preset {
+            name: name.to_string(),
+            ops: ops.to_vec(),
+        };
+        vec![
+            preset(
+                "comparable",
+                &[Eq, Ne, Lt, Lte, Gt, Gte, Between, In, NotIn],
+            ),
+            preset("text", &[Eq, Ne, Like, Ilike, In, NotIn]),
+            preset("membership", &[In, NotIn]),
+            preset("temporal", &[On, Before, Since, WithinLast]),
+        ]
+    }
+}
+
+/// What a spelling in an op list stands for.
+pub enum OpRef {
+    Op(Op),
+    Preset(Vec<Op>),
+}
+
+pub fn lookup_op(name: &str, presets: &[Preset]) -> Option<OpRef> {
+    if let Some(op) = Op::parse(name) {
+        return Some(OpRef::Op(op));
+    }
+    presets
+        .iter()
+        .find(|p| p.name == name)
+        .map(|p| OpRef::Preset(p.ops.clone()))
+}
+
+impl FieldPerms {
+    /// Add `other` on top of `self`, skipping ops that are already present.
+    pub fn extend(&mut self, other: FieldPerms) {
+        merge_ops(&mut self.select_where, other.select_where);
+        merge_ops(&mut self.update_where, other.update_where);
+        self.update_set |= other.update_set;
+        merge_ops(&mut self.delete_where, other.delete_where);
+        self.select_json.extend(other.select_json);
+        self.update_json.extend(other.update_json);
+        self.delete_json.extend(other.delete_json);
+        self.select_tuples.extend(other.select_tuples);
+        self.update_tuples.extend(other.update_tuples);
+        self.delete_tuples.extend(other.delete_tuples);
+    }
+
+    /// Keep only the ops for which `keep` holds.
+    fn retain(&mut self, keep: impl Fn(&WhereOp) -> bool) {
+        self.select_where.retain(&keep);
+        self.update_where.retain(&keep);
+        self.delete_where.retain(&keep);
+    }
+
+    pub fn all_where(&self) -> impl Iterator<Item = &WhereOp> {
+        self.select_where
+            .iter()
+            .chain(&self.update_where)
+            .chain(&self.delete_where)
+    }
+
+    fn all_json(&self) -> impl Iterator<Item = &JsonPath> {
+        self.select_json
+            .iter()
+            .chain(&self.update_json)
+            .chain(&self.delete_json)
+    }
+}
+
+fn merge_ops(target: &mut Vec<WhereOp>, ops: Vec<WhereOp>) {
+    for op in ops {
+        if !target.iter().any(|o| o.op == op.op) {
+            target.push(op);
+        }
+    }
 }
 
 /// Simplified model field info used by codegen.
 #[derive(Debug)]
 pub struct ModelInfoField {
     pub ident: syn::Ident,
+    /// Field name without `r#` (or its `rename`); prefixes the generated methods.
+    pub name: String,
+    /// `Column` variant `DeriveEntityModel` generates for the field.
+    pub column: syn::Ident,
     pub ty: syn::Type,
+    pub field_type: FieldType,
     pub perms: FieldPerms,
 }
 
+/// Simplified model info used by codegen.
+#[derive(Debug)]
+pub struct ModelInfo {
+    /// Builder name prefix: `<prefix>Select`, `<prefix>UpdateParams`, ...
+    pub prefix: syn::Ident,
+    /// Visibility of the builder and Params types (or of their module).
+    pub vis: syn::Visibility,
+    /// Submodule the builders are nested in, if any.
+    pub module: Option<Module>,
+    /// SeaORM `Entity` type, as seen from the model's module.
+    pub entity: syn::Path,
+    /// SeaORM `Column` enum, as seen from the model's module.
+    pub column: syn::Path,
+    /// Manifest the permissions were read from; the build depends on it.
+    pub manifest: Option<String>,
+    pub fields: Vec<ModelInfoField>,
+    /// Struct-level `search(fields = [..])`, for a `search` method on the select builder.
+    pub search: Option<Search>,
+    /// Struct-level `tuple_in(..)` per builder, in its `*_tuples` lists.
+    pub tuples: FieldPerms,
+}
+
+/// Fields searched together by the select builder's `search` method.
+#[derive(Debug)]
+pub struct Search {
+    /// Field names without `r#`, for `WhereParam::field`.
+    pub names: Vec<String>,
+    /// Their `Column` variants.
+    pub columns: Vec<syn::Ident>,
+}
+
+impl Search {
+    /// The struct-level key, which also names the method and `WhereParam::op`.
+    pub const KEY: &'static str = "search";
+}
+
+/// Struct-level `module(name = "..", select, ..)`.
+#[derive(Debug)]
+pub struct Module {
+    pub name: syn::Ident,
+    /// The builders gathered in it, as listed; each needs its derive.
+    pub builders: Vec<syn::Ident>,
+}
+
+impl Module {
+    /// Builders `module(..)` can list.
+    const BUILDERS: &'static [&'static str] = &["select", "update", "delete"];
+}
+
+/// Struct-level `#[sea_builder(...)]` settings.
+#[derive(Default)]
+struct StructConfig {
+    defaults: FieldPerms,
+    name: Option<syn::Ident>,
+    vis: Option<syn::Visibility>,
+    module: Option<Module>,
+    entity: Option<syn::Path>,
+    column: Option<syn::Path>,
+    config: Option<LitStr>,
+    presets: Vec<Preset>,
+    /// `search(fields = [..])` as written.
+    search: Option<(Span, Vec<syn::Ident>)>,
+    /// `tuple_in(..)` entries, moved out of `defaults` as they name fields.
+    tuples: FieldPerms,
+}
+
 /// Collect entity prefix and fields' permissions from a SeaORM model struct.
-pub fn collect(di: &DeriveInput) -> syn::Result<(String, Vec<ModelInfoField>)> {
+pub fn collect(di: &DeriveInput) -> syn::Result<ModelInfo> {
     // entity prefix from #[sea_orm(table_name = "...")]
-    let mut entity_prefix: Option<String> = None;
+    let mut table_name: Option<String> = None;
     for attr in &di.attrs {
         if attr.path().is_ident("sea_orm") {
             // #[sea_orm(table_name = "...")]
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("table_name") {
                     let lit: LitStr = meta.value()?.parse()?;
-                    entity_prefix = Some(to_camel(&lit.value()));
+                    table_name = Some(lit.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    // other SeaORM keys (`schema_name = ".."`, ...) are skipped
+                    meta.value()?.parse::<syn::Expr>()?;
                 }
                 Ok(())
             })?;
         }
     }
-    let entity_prefix = entity_prefix.unwrap_or_else(|| "Entity".to_string());
+    let config = parse_struct_attrs(&di.attrs)?;
+    let prefix = match config.name.clone() {
+        Some(name) => name,
+        None => match &table_name {
+            Some(table) => format_ident!("{}", to_camel(table)),
+            None => format_ident!("Entity"),
+        },
+    };
+    // `Column` lives next to `Entity` unless said otherwise
+    let entity = config
+        .entity
+        .clone()
+        .unwrap_or_else(|| syn::parse_quote!(self::Entity));
+    let column = config.column.clone().unwrap_or_else(|| {
+        let mut column = entity.clone();
+        if let Some(last) = column.segments.last_mut() {
+            *last = syn::parse_quote!(Column);
+        }
+        column
+    });
+
+    let manifest = manifest::load(
+        config.config.as_ref(),
+        table_name.as_deref(),
+        &config.presets,
+    )?;
 
     let mut fields_out: Vec<ModelInfoField> = Vec::new();
     let fields = match &di.data {
@@ -57,65 +583,916 @@ pub fn collect(di: &DeriveInput) -> syn::Result<(String, Vec<ModelInfoField>)> {
         _ => return Err(syn::Error::new_spanned(fields, "Expected named fields")),
     };
 
+    // report every bad field attribute at once rather than one per compile
+    let mut errors: Option<syn::Error> = None;
     for f in named {
-        let ident = f.ident.clone().expect("named");
-        let ty = f.ty.clone();
-        let perms = parse_sea_builder_attrs(&f.attrs)?;
-        fields_out.push(ModelInfoField { ident, ty, perms });
+        match parse_sea_builder_attrs(f, &config, manifest.as_ref()) {
+            Ok(field) => fields_out.push(field),
+            Err(err) => match errors.as_mut() {
+                Some(all) => all.combine(err),
+                None => errors = Some(err),
+            },
+        }
+    }
+    if let Some(err) = errors {
+        return Err(err);
+    }
+    if let Some(manifest) = &manifest {
+        let names: Vec<String> = fields_out
+            .iter()
+            .map(|f| f.ident.unraw().to_string())
+            .collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        for (field, _) in &manifest.fields {
+            if !names.contains(&field.as_str()) {
+                return Err(manifest.error(unknown_msg(field, "field", &names)));
+            }
+        }
     }
-    Ok((entity_prefix, fields_out))
+    check_col_partners(&fields_out)?;
+    check_tuples(&config.tuples, &fields_out)?;
+    let search = match &config.search {
+        Some((span, idents)) => Some(resolve_search(*span, idents, &fields_out)?),
+        None => None,
+    };
+    Ok(ModelInfo {
+        prefix,
+        vis: config
+            .vis
+            .unwrap_or_else(|| syn::Visibility::Public(Default::default())),
+        module: config.module,
+        entity,
+        column,
+        manifest: manifest.map(|m| m.path),
+        fields: fields_out,
+        search,
+        tuples: config.tuples,
+    })
 }
 
-fn parse_sea_builder_attrs(attrs: &Vec<Attribute>) -> syn::Result<FieldPerms> {
-    let mut perms = FieldPerms::default();
+/// Reject `tuple_in` entries naming unknown or repeated fields, or fewer
+/// than two.
+fn check_tuples(tuples: &FieldPerms, fields: &[ModelInfoField]) -> syn::Result<()> {
+    let names: Vec<String> = fields.iter().map(|f| f.ident.unraw().to_string()).collect();
+    let known: Vec<&str> = names.iter().map(String::as_str).collect();
+    let modes = [
+        ("select", &tuples.select_tuples),
+        ("update", &tuples.update_tuples),
+        ("delete", &tuples.delete_tuples),
+    ];
+    for (mode, list) in modes {
+        let mut seen: Vec<Vec<String>> = Vec::new();
+        for tuple in list {
+            if tuple.fields.len() < 2 {
+                return Err(syn::Error::new(
+                    tuple.span,
+                    "`tuple_in` needs at least two fields; use `in` for one",
+                ));
+            }
+            let mut listed: Vec<String> = Vec::new();
+            for ident in &tuple.fields {
+                let name = ident.unraw().to_string();
+                if !known.contains(&name.as_str()) {
+                    return Err(unknown(&ident.clone().into(), "field", &known));
+                }
+                if listed.contains(&name) {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("duplicate field `{}` in `tuple_in`", name),
+                    ));
+                }
+                listed.push(name);
+            }
+            if seen.contains(&listed) {
+                return Err(syn::Error::new(
+                    tuple.span,
+                    format!("duplicate `tuple_in({})` in `{}`", listed.join(", "), mode),
+                ));
+            }
+            seen.push(listed);
+        }
+    }
+    Ok(())
+}
+
+/// Look up the `search(fields = [..])` fields, which must be textual.
+fn resolve_search(
+    span: Span,
+    idents: &[syn::Ident],
+    fields: &[ModelInfoField],
+) -> syn::Result<Search> {
+    if backend().is_none() {
+        return Err(no_backend_err(span, "`search`"));
+    }
+    if idents.is_empty() {
+        return Err(syn::Error::new(span, "`search` needs at least one field"));
+    }
+    let names: Vec<String> = fields.iter().map(|f| f.ident.unraw().to_string()).collect();
+    let known: Vec<&str> = names.iter().map(String::as_str).collect();
+    let mut search = Search {
+        names: Vec::new(),
+        columns: Vec::new(),
+    };
+    for ident in idents {
+        let name = ident.unraw().to_string();
+        let field = match fields.iter().find(|f| f.ident.unraw() == name) {
+            Some(field) => field,
+            None => return Err(unknown(&ident.clone().into(), "field", &known)),
+        };
+        if search.names.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("duplicate field `{}` in `search`", name),
+            ));
+        }
+        if field.field_type.class != TypeClass::Textual {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "`search` needs textual fields, but `{}` is {}",
+                    name,
+                    field.field_type.class.name()
+                ),
+            ));
+        }
+        search.names.push(name);
+        search.columns.push(field.column.clone());
+    }
+    Ok(search)
+}
+
+/// Keys accepted at struct level.
+const STRUCT_KEYS: &[&str] = &[
+    "select", "update", "delete", "name", "vis", "module", "entity", "column", "config", "preset",
+    "search",
+];
+/// Keys accepted at field level besides the modes.
+const FIELD_KEYS: &[&str] = &[
+    "select", "update", "delete", "skip", "override", "kind", "rename",
+];
+
+/// Struct-level `#[sea_builder(select(..), update(..), delete(..))]` becomes the
+/// default `FieldPerms` of every field; `name`, `vis`, `module`, `entity` and
+/// `column` configure the generated types and `config` points at a manifest.
+fn parse_struct_attrs(attrs: &[Attribute]) -> syn::Result<StructConfig> {
+    let mut config = StructConfig {
+        presets: parse_presets(attrs)?,
+        ..StructConfig::default()
+    };
     for attr in attrs {
         if !attr.path().is_ident("sea_builder") {
             continue;
         }
         attr.parse_nested_meta(|meta| {
-            if meta.path.is_ident("select") {
-                meta.parse_nested_meta(|m2| {
-                    if m2.path.is_ident("where") {
-                        parse_ops_nested(&m2, &mut perms.select_where)
-                    } else {
-                        Ok(())
-                    }
-                })?;
-            } else if meta.path.is_ident("update") {
-                meta.parse_nested_meta(|m2| {
-                    if m2.path.is_ident("where") {
-                        parse_ops_nested(&m2, &mut perms.update_where)
-                    } else if m2.path.is_ident("set") {
-                        perms.update_set = true;
-                        Ok(())
-                    } else {
-                        Ok(())
-                    }
-                })?;
-            } else if meta.path.is_ident("delete") {
-                meta.parse_nested_meta(|m2| {
-                    if m2.path.is_ident("where") {
-                        parse_ops_nested(&m2, &mut perms.delete_where)
-                    } else {
-                        Ok(())
-                    }
-                })?;
+            if meta.path.is_ident("preset") {
+                // read up front by `parse_presets`
+                skip_meta(&meta)
+            } else if meta.path.is_ident("name") {
+                set_once(&meta, &mut config.name)
+            } else if meta.path.is_ident("vis") {
+                set_once(&meta, &mut config.vis)
+            } else if meta.path.is_ident("module") {
+                parse_module(&meta, &mut config.module)
+            } else if meta.path.is_ident("entity") {
+                set_once(&meta, &mut config.entity)
+            } else if meta.path.is_ident("column") {
+                set_once(&meta, &mut config.column)
+            } else if meta.path.is_ident("config") {
+                lit_once(&meta, &mut config.config)
+            } else if meta.path.is_ident(Search::KEY) {
+                parse_search(&meta, &mut config.search)
+            } else if parse_perms_meta(&meta, &config.presets, &mut config.defaults)? {
+                Ok(())
+            } else {
+                Err(unknown(&meta.path, "struct-level key", STRUCT_KEYS))
+            }
+        })?;
+    }
+    if let Some(path) = config.defaults.all_json().next() {
+        return Err(syn::Error::new(
+            path.span,
+            "`json_path` names keys of one field and can't be a struct-level default",
+        ));
+    }
+    let defaults = &mut config.defaults;
+    config.tuples.select_tuples = std::mem::take(&mut defaults.select_tuples);
+    config.tuples.update_tuples = std::mem::take(&mut defaults.update_tuples);
+    config.tuples.delete_tuples = std::mem::take(&mut defaults.delete_tuples);
+    if let Some(w) = config
+        .defaults
+        .all_where()
+        .find(|w| w.op.col_base().is_some())
+    {
+        return Err(syn::Error::new(
+            w.span,
+            "column comparisons are opted into per field and can't be a struct-level default",
+        ));
+    }
+    Ok(config)
+}
+
+/// `module(name = "builders", select, update)`, rejecting a second occurrence.
+fn parse_module(meta: &syn::meta::ParseNestedMeta, slot: &mut Option<Module>) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(&meta.path, "duplicate `module`"));
+    }
+    if meta.input.peek(Token![=]) {
+        return Err(meta.error(
+            "`module` lists the builders it gathers: `module(name = \"..\", select, update)`",
+        ));
+    }
+    let mut name: Option<syn::Ident> = None;
+    let mut builders: Vec<syn::Ident> = Vec::new();
+    meta.parse_nested_meta(|m| {
+        if m.path.is_ident("name") {
+            return set_once(&m, &mut name);
+        }
+        let ident = m.path.require_ident()?;
+        if !Module::BUILDERS.iter().any(|b| ident == b) {
+            let known = [&["name"], Module::BUILDERS].concat();
+            return Err(unknown(&m.path, "`module` key", &known));
+        }
+        if builders.contains(ident) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("duplicate `{}`", ident),
+            ));
+        }
+        builders.push(ident.clone());
+        Ok(())
+    })?;
+    let Some(name) = name else {
+        return Err(meta.error("`module` needs `name = \"..\"`"));
+    };
+    if builders.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &name,
+            "`module` lists no builders; name the derived ones, e.g. `select, update`",
+        ));
+    }
+    *slot = Some(Module { name, builders });
+    Ok(())
+}
+
+/// `search(fields = [a, b])`, rejecting a second occurrence.
+fn parse_search(
+    meta: &syn::meta::ParseNestedMeta,
+    slot: &mut Option<(Span, Vec<syn::Ident>)>,
+) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(&meta.path, "duplicate `search`"));
+    }
+    let mut fields = None;
+    meta.parse_nested_meta(|m| {
+        if !m.path.is_ident("fields") {
+            return Err(unknown(&m.path, "`search` key", &["fields"]));
+        }
+        let value = m.value()?;
+        let content;
+        syn::bracketed!(content in value);
+        let idents = Punctuated::<syn::Ident, Token![,]>::parse_terminated_with(
+            &content,
+            syn::Ident::parse_any,
+        )?;
+        fields = Some(idents.into_iter().collect());
+        Ok(())
+    })?;
+    let span = syn::spanned::Spanned::span(&meta.path);
+    *slot = Some((span, fields.unwrap_or_default()));
+    Ok(())
+}
+
+/// Built-in presets plus the struct's `preset(name = [ops..], ..)` entries.
+/// Read before anything else so presets can be used in any order.
+fn parse_presets(attrs: &[Attribute]) -> syn::Result<Vec<Preset>> {
+    let mut presets = Preset::builtins();
+    for attr in attrs {
+        if !attr.path().is_ident("sea_builder") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("preset") {
+                return skip_meta(&meta);
+            }
+            meta.parse_nested_meta(|entry| {
+                let name = path_name(&entry.path);
+                if lookup_op(&name, &presets).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &entry.path,
+                        format!("preset `{}` clashes with an existing op or preset", name),
+                    ));
+                }
+                let content;
+                let value = entry.value()?;
+                syn::bracketed!(content in value);
+                let spellings = Punctuated::<syn::Ident, Token![,]>::parse_terminated_with(
+                    &content,
+                    syn::Ident::parse_any,
+                )?;
+                let mut ops: Vec<WhereOp> = Vec::new();
+                for spelling in spellings {
+                    let context = format!("preset({} = [..])", name);
+                    push_op(&spelling.into(), &context, &presets, &mut ops)?;
+                }
+                if ops.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &entry.path,
+                        format!("preset `{}` has no ops", name),
+                    ));
+                }
+                presets.push(Preset {
+                    name,
+                    ops: ops.into_iter().map(|w| w.op).collect(),
+                });
+                Ok(())
+            })
+        })?;
+    }
+    Ok(presets)
+}
+
+/// Consume a nested meta entry without interpreting it.
+fn skip_meta(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::Group>()?;
+    }
+    Ok(())
+}
+
+/// Keep `key = "..."` as a literal in `slot`, rejecting a second occurrence.
+fn lit_once(meta: &syn::meta::ParseNestedMeta, slot: &mut Option<LitStr>) -> syn::Result<()> {
+    let lit: LitStr = meta.value()?.parse()?;
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(
+            &meta.path,
+            format!("duplicate `{}`", path_name(&meta.path)),
+        ));
+    }
+    *slot = Some(lit);
+    Ok(())
+}
+
+/// Parse `key = "..."` into `slot`, rejecting a second occurrence.
+fn set_once<T: syn::parse::Parse>(
+    meta: &syn::meta::ParseNestedMeta,
+    slot: &mut Option<T>,
+) -> syn::Result<()> {
+    let lit: LitStr = meta.value()?.parse()?;
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(
+            &meta.path,
+            format!("duplicate `{}`", path_name(&meta.path)),
+        ));
+    }
+    *slot = Some(lit.parse()?);
+    Ok(())
+}
+
+/// Field-level permissions. They extend the struct defaults unless the field
+/// says `override`; `skip` opts the field out entirely.
+///
+/// Explicit ops that can't apply to the field's type are errors; default ops
+/// that can't (or can't be shown to) apply are simply not given to the field.
+fn parse_sea_builder_attrs(
+    f: &syn::Field,
+    config: &StructConfig,
+    manifest: Option<&Manifest>,
+) -> syn::Result<ModelInfoField> {
+    let ident = f.ident.clone().expect("named");
+    let mut perms = FieldPerms::default();
+    let mut field_type = classify(&f.ty);
+    let mut rename: Option<LitStr> = None;
+    let mut skip = false;
+    let mut replace = false;
+    for attr in &f.attrs {
+        if !attr.path().is_ident("sea_builder") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+            } else if meta.path.is_ident("override") {
+                replace = true;
+            } else if meta.path.is_ident("kind") {
+                let lit: LitStr = meta.value()?.parse()?;
+                field_type.class = parse_kind(&lit)?;
+            } else if meta.path.is_ident("rename") {
+                lit_once(&meta, &mut rename)?;
+                let lit = rename.as_ref().expect("just set");
+                if !is_method_prefix(&lit.value()) {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "`rename` must be a snake_case identifier",
+                    ));
+                }
+            } else if !parse_perms_meta(&meta, &config.presets, &mut perms)? {
+                return Err(unknown(&meta.path, "key", FIELD_KEYS));
             }
             Ok(())
         })?;
     }
-    Ok(perms)
+    let unraw = ident.unraw().to_string();
+    let mut field = ModelInfoField {
+        name: rename
+            .map(|lit| lit.value())
+            .unwrap_or_else(|| unraw.clone()),
+        column: column_variant(&f.attrs, &unraw)?,
+        ident,
+        ty: f.ty.clone(),
+        field_type,
+        perms: FieldPerms::default(),
+    };
+    if skip {
+        return Ok(field);
+    }
+    let tuples = [
+        &perms.select_tuples,
+        &perms.update_tuples,
+        &perms.delete_tuples,
+    ];
+    if let Some(tuple) = tuples.into_iter().flatten().next() {
+        return Err(syn::Error::new(
+            tuple.span,
+            "`tuple_in` names several fields and goes in the struct-level attribute",
+        ));
+    }
+    check_support(&field, &perms)?;
+    check_json_paths(&field, &perms)?;
+    // manifest entries count as written on the field
+    if let Some(manifest) = manifest {
+        if let Some(listed) = manifest.perms(&unraw) {
+            check_support(&field, listed).map_err(|err| manifest.error(err))?;
+            perms.extend(listed.clone());
+        }
+    }
+    if replace {
+        field.perms = perms;
+        return Ok(field);
+    }
+    let mut merged = config.defaults.clone();
+    let field_type = &field.field_type;
+    merged.retain(|w| support(w.op, field_type) == Support::Yes);
+    merged.extend(perms);
+    field.perms = merged;
+    Ok(field)
 }
 
-fn parse_ops_nested(meta: &syn::meta::ParseNestedMeta, target: &mut Vec<String>) -> syn::Result<()> {
+/// The fields `field` may be compared with by its `<op>_col` methods: the
+/// others of the same type.
+pub fn col_partners<'a>(
+    field: &ModelInfoField,
+    fields: &'a [ModelInfoField],
+) -> Vec<&'a ModelInfoField> {
+    let ty = quote::ToTokens::to_token_stream(&field.field_type.inner).to_string();
+    fields
+        .iter()
+        .filter(|g| g.name != field.name)
+        .filter(|g| quote::ToTokens::to_token_stream(&g.field_type.inner).to_string() == ty)
+        .collect()
+}
+
+/// Reject `<op>_col` ops on fields that have nothing to be compared with.
+pub fn check_col_partners(fields: &[ModelInfoField]) -> syn::Result<()> {
+    for field in fields {
+        let Some(w) = field.perms.all_where().find(|w| w.op.col_base().is_some()) else {
+            continue;
+        };
+        if col_partners(field, fields).is_empty() {
+            return Err(syn::Error::new(
+                w.span,
+                format!(
+                    "op `{}` on `{}` needs another `{}` field to compare with",
+                    w.op.name(),
+                    field.ident.unraw(),
+                    quote::ToTokens::to_token_stream(&field.field_type.inner),
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reject explicitly requested ops that can't apply to `field`'s type.
+pub fn check_support(field: &ModelInfoField, perms: &FieldPerms) -> syn::Result<()> {
+    let field_type = &field.field_type;
+    for w in perms.all_where() {
+        if support(w.op, field_type) != Support::No {
+            continue;
+        }
+        if w.op.needs_backend() && backend().is_none() {
+            return Err(no_backend_err(w.span, &format!("op `{}`", w.op.name())));
+        }
+        let msg = if field_type.class == TypeClass::Array && w.op.array_shape().is_some() {
+            format!(
+                "op `{}` on array field `{}` needs the `postgres` feature of sea_orm_builder",
+                w.op.name(),
+                field.ident.unraw()
+            )
+        } else if w.op.bitflag() {
+            format!(
+                "op `{}` needs an integer field, but `{}` is `{}`",
+                w.op.name(),
+                field.ident.unraw(),
+                quote::ToTokens::to_token_stream(&field_type.inner)
+            )
+        } else if w.op.temporal() {
+            format!(
+                "op `{}` needs a `DateTimeUtc` or `Date` field, but `{}` is `{}`",
+                w.op.name(),
+                field.ident.unraw(),
+                quote::ToTokens::to_token_stream(&field_type.inner)
+            )
+        } else if w.op.nullable_only() {
+            format!(
+                "op `{}` needs a nullable field, but `{}` is not an `Option<_>`",
+                w.op.name(),
+                field.ident.unraw()
+            )
+        } else {
+            format!(
+                "op `{}` is not supported on {}{} field `{}`",
+                w.op.name(),
+                if field_type.optional { "optional " } else { "" },
+                field_type.class.name(),
+                field.ident.unraw()
+            )
+        };
+        return Err(syn::Error::new(w.span, msg));
+    }
+    Ok(())
+}
+
+/// Reject `json_path` keys on non-JSON fields, or with types and ops the
+/// JSON extraction can't compare.
+fn check_json_paths(field: &ModelInfoField, perms: &FieldPerms) -> syn::Result<()> {
+    for path in perms.all_json() {
+        if backend().is_none() {
+            return Err(no_backend_err(path.span, "`json_path`"));
+        }
+        if field.field_type.class != TypeClass::Json {
+            return Err(syn::Error::new(
+                path.span,
+                format!(
+                    "`json_path` needs a json field, but `{}` is {}",
+                    field.ident.unraw(),
+                    field.field_type.class.name()
+                ),
+            ));
+        }
+        let class = path.field_type.class;
+        if !matches!(
+            class,
+            TypeClass::Textual | TypeClass::Numeric | TypeClass::Boolean
+        ) {
+            return Err(syn::Error::new(
+                path.span,
+                format!(
+                    "`json_path` key `{}` must be textual, numeric or boolean, not {}",
+                    path.key,
+                    class.name()
+                ),
+            ));
+        }
+        for w in &path.ops {
+            if !w.op.json_supported() || support(w.op, &path.field_type) != Support::Yes {
+                return Err(syn::Error::new(
+                    w.span,
+                    format!(
+                        "op `{}` is not supported on {} json key `{}`",
+                        w.op.name(),
+                        class.name(),
+                        path.key
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The `Column` variant `DeriveEntityModel` derives from a field: its
+/// `enum_name` if set, otherwise the UpperCamel field name, with `Self`
+/// escaped the way SeaORM escapes it.
+pub fn column_variant(attrs: &[Attribute], field_name: &str) -> syn::Result<syn::Ident> {
+    let mut enum_name: Option<String> = None;
+    for attr in attrs {
+        if attr.path().is_ident("sea_orm") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("enum_name") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    enum_name = Some(lit.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            })?;
+        }
+    }
+    let variant = enum_name.unwrap_or_else(|| to_camel(field_name));
+    if variant == "Self" {
+        return Ok(format_ident!("Self_"));
+    }
+    Ok(format_ident!("{}", variant))
+}
+
+/// Whether `s` can start a method name: `[a-z_][a-z0-9_]*`.
+pub fn is_method_prefix(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn parse_kind(lit: &LitStr) -> syn::Result<TypeClass> {
+    let value = lit.value();
+    match TypeClass::KINDS.iter().find(|(name, _)| *name == value) {
+        Some((_, class)) => Ok(*class),
+        None => {
+            let names: Vec<&str> = TypeClass::KINDS.iter().map(|(name, _)| *name).collect();
+            Err(syn::Error::new_spanned(
+                lit,
+                unknown_msg(&value, "kind", &names),
+            ))
+        }
+    }
+}
+
+/// Parse one `select(..)` / `update(..)` / `delete(..)` entry into `perms`.
+/// Returns `false` when `meta` is none of those.
+fn parse_perms_meta(
+    meta: &syn::meta::ParseNestedMeta,
+    presets: &[Preset],
+    perms: &mut FieldPerms,
+) -> syn::Result<bool> {
+    if meta.path.is_ident("select") {
+        meta.parse_nested_meta(|m2| {
+            if m2.path.is_ident("where") {
+                parse_ops_nested(
+                    &m2,
+                    "select",
+                    presets,
+                    &mut perms.select_where,
+                    &mut perms.select_json,
+                    &mut perms.select_tuples,
+                )
+            } else {
+                Err(unknown(&m2.path, "`select` key", &["where"]))
+            }
+        })?;
+    } else if meta.path.is_ident("update") {
+        meta.parse_nested_meta(|m2| {
+            if m2.path.is_ident("where") {
+                parse_ops_nested(
+                    &m2,
+                    "update",
+                    presets,
+                    &mut perms.update_where,
+                    &mut perms.update_json,
+                    &mut perms.update_tuples,
+                )
+            } else if m2.path.is_ident("set") {
+                if perms.update_set {
+                    return Err(syn::Error::new_spanned(&m2.path, "duplicate `set`"));
+                }
+                perms.update_set = true;
+                Ok(())
+            } else {
+                Err(unknown(&m2.path, "`update` key", &["where", "set"]))
+            }
+        })?;
+    } else if meta.path.is_ident("delete") {
+        meta.parse_nested_meta(|m2| {
+            if m2.path.is_ident("where") {
+                parse_ops_nested(
+                    &m2,
+                    "delete",
+                    presets,
+                    &mut perms.delete_where,
+                    &mut perms.delete_json,
+                    &mut perms.delete_tuples,
+                )
+            } else {
+                Err(unknown(&m2.path, "`delete` key", &["where"]))
+            }
+        })?;
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn parse_ops_nested(
+    meta: &syn::meta::ParseNestedMeta,
+    mode: &str,
+    presets: &[Preset],
+    target: &mut Vec<WhereOp>,
+    json: &mut Vec<JsonPath>,
+    tuples: &mut Vec<TupleIn>,
+) -> syn::Result<()> {
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("tuple_in") && inner.input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in inner.input);
+            let fields = Punctuated::<syn::Ident, Token![,]>::parse_terminated_with(
+                &content,
+                syn::Ident::parse_any,
+            )?;
+            tuples.push(TupleIn {
+                span: syn::spanned::Spanned::span(&inner.path),
+                fields: fields.into_iter().collect(),
+            });
+            return Ok(());
+        }
+        if inner.path.is_ident("json_path") && inner.input.peek(syn::token::Paren) {
+            return parse_json_path(&inner, mode, presets, json);
+        }
+        if inner.path.is_ident("cmp_col") && inner.input.peek(syn::token::Paren) {
+            return parse_cmp_col(&inner, mode, target);
+        }
+        if inner.input.peek(syn::token::Paren) || inner.input.peek(syn::Token![=]) {
+            let name = path_name(&inner.path);
+            // unknown ops still get the "unknown op" error first
+            if lookup_op(&name, presets).is_none() {
+                return Err(unknown(&inner.path, "op", &op_spellings(presets)));
+            }
+            return Err(syn::Error::new_spanned(
+                &inner.path,
+                format!("op `{}` takes no arguments", name),
+            ));
+        }
+        push_op(
+            &inner.path,
+            &format!("{}(where(..))", mode),
+            presets,
+            target,
+        )
+    })
+}
+
+/// `cmp_col(gt, lt, ..)` inside `where(..)`: the `<op>_col` forms of
+/// comparison ops.
+fn parse_cmp_col(
+    meta: &syn::meta::ParseNestedMeta,
+    mode: &str,
+    target: &mut Vec<WhereOp>,
+) -> syn::Result<()> {
+    const COMPARISONS: &[&str] = &["eq", "ne", "lt", "lte", "gt", "gte"];
     meta.parse_nested_meta(|inner| {
-        if let Some(ident) = inner.path.get_ident() {
-            target.push(ident.to_string());
+        let name = path_name(&inner.path);
+        let op = match Op::parse(&name).and_then(Op::to_col) {
+            Some(op) => op,
+            None => return Err(unknown(&inner.path, "`cmp_col` op", COMPARISONS)),
+        };
+        if target.iter().any(|w| w.op == op) {
+            return Err(syn::Error::new_spanned(
+                &inner.path,
+                format!("duplicate op `{}` in `{}(where(..))`", op.name(), mode),
+            ));
         }
+        target.push(WhereOp {
+            op,
+            span: syn::spanned::Spanned::span(&inner.path),
+        });
         Ok(())
     })
 }
 
+/// `json_path(key: Type = [ops..], ..)` inside `where(..)`.
+fn parse_json_path(
+    meta: &syn::meta::ParseNestedMeta,
+    mode: &str,
+    presets: &[Preset],
+    json: &mut Vec<JsonPath>,
+) -> syn::Result<()> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    while !content.is_empty() {
+        // keys are JSON names, so keywords are fine
+        let key = content.call(syn::Ident::parse_any)?.unraw();
+        content.parse::<Token![:]>()?;
+        let ty: syn::Type = content.parse()?;
+        content.parse::<Token![=]>()?;
+        let ops;
+        syn::bracketed!(ops in content);
+        let name = key.to_string();
+        if json.iter().any(|p| p.key == name) {
+            return Err(syn::Error::new_spanned(
+                &key,
+                format!(
+                    "duplicate key `{}` in `{}(where(json_path(..)))`",
+                    name, mode
+                ),
+            ));
+        }
+        let context = format!("{}(where(json_path({}: ..)))", mode, name);
+        let mut path = JsonPath {
+            span: key.span(),
+            key: name,
+            field_type: classify(&ty),
+            ops: Vec::new(),
+        };
+        let names = Punctuated::<syn::Ident, Token![,]>::parse_terminated_with(
+            &ops,
+            syn::Ident::parse_any,
+        )?;
+        for op in names {
+            push_op(&op.into(), &context, presets, &mut path.ops)?;
+        }
+        json.push(path);
+        if !content.is_empty() {
+            content.parse::<Token![,]>()?;
+        }
+    }
+    Ok(())
+}
+
+/// Add an op, or every op of a preset, to `target`. Listing an op twice
+/// within `context` is an error; ops a preset adds again are not, since
+/// presets overlap by design.
+pub fn push_op(
+    path: &syn::Path,
+    context: &str,
+    presets: &[Preset],
+    target: &mut Vec<WhereOp>,
+) -> syn::Result<()> {
+    let span = syn::spanned::Spanned::span(path);
+    match lookup_op(&path_name(path), presets) {
+        Some(OpRef::Op(op)) => {
+            if target.iter().any(|w| w.op == op) {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!("duplicate op `{}` in `{}`", op.name(), context),
+                ));
+            }
+            target.push(WhereOp { op, span });
+        }
+        Some(OpRef::Preset(ops)) => {
+            let ops = ops.into_iter().map(|op| WhereOp { op, span }).collect();
+            merge_ops(target, ops);
+        }
+        None => return Err(unknown(path, "op", &op_spellings(presets))),
+    }
+    Ok(())
+}
+
+/// Op names, aliases and preset names, for suggestions.
+pub fn op_spellings(presets: &[Preset]) -> Vec<&str> {
+    Op::ALL
+        .iter()
+        .flat_map(|op| std::iter::once(op.name()).chain(op.aliases().iter().copied()))
+        .chain(presets.iter().map(|p| p.name.as_str()))
+        .collect()
+}
+
+/// Error for an unrecognised attribute token, spanned on the token itself and
+/// suggesting the closest known spelling.
+pub fn unknown(path: &syn::Path, what: &str, known: &[&str]) -> syn::Error {
+    syn::Error::new_spanned(path, unknown_msg(&path_name(path), what, known))
+}
+
+pub fn unknown_msg(name: &str, what: &str, known: &[&str]) -> String {
+    let mut msg = format!("unknown {} `{}`", what, name);
+    match closest(name, known) {
+        Some(candidate) => msg.push_str(&format!("; did you mean `{}`?", candidate)),
+        None => msg.push_str(&format!("; expected one of: {}", known.join(", "))),
+    }
+    msg
+}
+
+fn path_name(path: &syn::Path) -> String {
+    match path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => quote::ToTokens::to_token_stream(path)
+            .to_string()
+            .replace(' ', ""),
+    }
+}
+
+/// Closest candidate within an edit distance of roughly a third of its length.
+fn closest<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|c| (levenshtein(input, c), *c))
+        .filter(|(d, c)| *d <= (c.len() / 3).max(1))
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { prev[j] } else { prev[j] + 1 };
+            cur.push(cost.min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
 pub fn to_camel(s: &str) -> String {
     s.to_upper_camel_case()
 }
@@ -0,0 +1,278 @@
+//! Parsing of the function-like `builders!` macro
+//!
+//! - Names an existing entity module instead of annotating its `Model`, so
+//!   generated entity files can be regenerated freely
+//! - Declares the field types (a macro can't see the model) and checks them
+//!   against `<module>::Model` at compile time
+//! - Lists permissions per builder: `select { id: [eq, in] }`,
+//!   `update { status: [set] }`, `delete { id: [eq] }`
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, bracketed, Ident, LitStr, Token};
+
+use crate::ast::{
+    check_col_partners, check_support, column_variant, push_op, to_camel, unknown, FieldPerms,
+    ModelInfo, ModelInfoField, Preset,
+};
+use crate::gen::{expand_mode, Mode};
+use crate::types::classify;
+
+/// Keys accepted after the module path.
+const KEYS: &[&str] = &["name", "vis", "fields", "select", "update", "delete"];
+
+/// `builders!(path::to::entity, name = "..", fields { .. }, select { .. }, ..)`
+struct BuildersInput {
+    module: syn::Path,
+    name: Option<Ident>,
+    vis: Option<syn::Visibility>,
+    fields: Vec<FieldDecl>,
+    sections: Vec<(Mode, Vec<FieldEntry>)>,
+}
+
+/// `id: i64` in `fields { .. }`, or `id: i64 as MyId` when the `Column`
+/// variant isn't the CamelCase field name (`#[sea_orm(enum_name = "..")]`).
+struct FieldDecl {
+    ident: Ident,
+    ty: syn::Type,
+    variant: Option<Ident>,
+}
+
+/// `id: [eq, in]` in a builder section.
+struct FieldEntry {
+    ident: Ident,
+    ops: Vec<Ident>,
+}
+
+impl Parse for BuildersInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut out = BuildersInput {
+            module: input.parse()?,
+            name: None,
+            vis: None,
+            fields: Vec::new(),
+            sections: Vec::new(),
+        };
+        let mut seen_fields = false;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            let duplicate = || syn::Error::new_spanned(&key, format!("duplicate `{}`", key));
+            match key.to_string().as_str() {
+                "name" | "vis" => {
+                    input.parse::<Token![=]>()?;
+                    let lit: LitStr = input.parse()?;
+                    if key == "name" {
+                        if out.name.is_some() {
+                            return Err(duplicate());
+                        }
+                        out.name = Some(lit.parse()?);
+                    } else {
+                        if out.vis.is_some() {
+                            return Err(duplicate());
+                        }
+                        out.vis = Some(lit.parse()?);
+                    }
+                }
+                "fields" => {
+                    if seen_fields {
+                        return Err(duplicate());
+                    }
+                    seen_fields = true;
+                    let content;
+                    braced!(content in input);
+                    out.fields = Punctuated::<FieldDecl, Token![,]>::parse_terminated(&content)?
+                        .into_iter()
+                        .collect();
+                }
+                other => {
+                    let mode = match Mode::ALL.iter().find(|m| m.key() == other) {
+                        Some(mode) => *mode,
+                        None => return Err(unknown(&key.clone().into(), "key", KEYS)),
+                    };
+                    if out.sections.iter().any(|(m, _)| *m == mode) {
+                        return Err(duplicate());
+                    }
+                    let content;
+                    braced!(content in input);
+                    let entries = Punctuated::<FieldEntry, Token![,]>::parse_terminated(&content)?;
+                    out.sections.push((mode, entries.into_iter().collect()));
+                }
+            }
+        }
+        if !seen_fields && !out.sections.is_empty() {
+            return Err(
+                input.error("expected `fields { name: Type, .. }` declaring the field types")
+            );
+        }
+        Ok(out)
+    }
+}
+
+impl Parse for FieldDecl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        let variant = match input.parse::<Option<Token![as]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+        Ok(FieldDecl { ident, ty, variant })
+    }
+}
+
+impl Parse for FieldEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let content;
+        bracketed!(content in input);
+        // `in` is a keyword, so ops are parsed as any identifier
+        let ops =
+            Punctuated::<Ident, Token![,]>::parse_terminated_with(&content, Ident::parse_any)?;
+        Ok(FieldEntry {
+            ident,
+            ops: ops.into_iter().collect(),
+        })
+    }
+}
+
+/// Entry point used by `builders!` in lib.rs
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as BuildersInput);
+    match build(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn build(input: &BuildersInput) -> syn::Result<proc_macro2::TokenStream> {
+    let module = &input.module;
+    let prefix = match &input.name {
+        Some(name) => name.clone(),
+        None => {
+            let last = module.segments.last().expect("parsed path").ident.unraw();
+            format_ident!("{}", to_camel(&last.to_string()))
+        }
+    };
+
+    let mut fields = Vec::new();
+    for decl in &input.fields {
+        let name = decl.ident.unraw().to_string();
+        if fields.iter().any(|f: &ModelInfoField| f.name == name) {
+            return Err(syn::Error::new_spanned(
+                &decl.ident,
+                format!("duplicate field `{}`", name),
+            ));
+        }
+        // spanned so a variant the entity lacks is reported on this field
+        let column = match &decl.variant {
+            Some(variant) => variant.clone(),
+            None => Ident::new(&column_variant(&[], &name)?.to_string(), decl.ident.span()),
+        };
+        fields.push(ModelInfoField {
+            column,
+            name,
+            ident: decl.ident.clone(),
+            ty: decl.ty.clone(),
+            field_type: classify(&decl.ty),
+            perms: FieldPerms::default(),
+        });
+    }
+    let presets = Preset::builtins();
+    let known: Vec<String> = fields.iter().map(|f| f.name.clone()).collect();
+    let known: Vec<&str> = known.iter().map(String::as_str).collect();
+
+    for (mode, entries) in &input.sections {
+        let mut seen: Vec<String> = Vec::new();
+        for entry in entries {
+            let name = entry.ident.unraw().to_string();
+            let field = match fields.iter_mut().find(|f| f.name == name) {
+                Some(field) => field,
+                None => return Err(unknown(&entry.ident.clone().into(), "field", &known)),
+            };
+            if seen.contains(&name) {
+                return Err(syn::Error::new_spanned(
+                    &entry.ident,
+                    format!("duplicate field `{}` in `{}`", name, mode.key()),
+                ));
+            }
+            seen.push(name.clone());
+            let context = format!("{} {{ {}: [..] }}", mode.key(), name);
+            let mut perms = FieldPerms::default();
+            for op in &entry.ops {
+                if *mode == Mode::Update && op == "set" {
+                    if perms.update_set {
+                        return Err(syn::Error::new_spanned(op, "duplicate `set`"));
+                    }
+                    perms.update_set = true;
+                    continue;
+                }
+                let target = match mode {
+                    Mode::Select => &mut perms.select_where,
+                    Mode::Update => &mut perms.update_where,
+                    Mode::Delete => &mut perms.delete_where,
+                };
+                push_op(&op.clone().into(), &context, &presets, target)?;
+            }
+            check_support(field, &perms)?;
+            field.perms.extend(perms);
+        }
+    }
+
+    check_col_partners(&fields)?;
+
+    // The declared types must match the entity's, so regenerating the entity
+    // with a changed column type fails here rather than at some later call.
+    // Likewise the `Column` variant must exist, e.g. after an `enum_name`.
+    let type_checks: Vec<_> = input
+        .fields
+        .iter()
+        .zip(&fields)
+        .map(|(decl, field)| {
+            let (ident, ty) = (&decl.ident, &decl.ty);
+            let variant = &field.column;
+            let column_check = quote_spanned! {variant.span()=>
+                let _ = #module::Column::#variant;
+            };
+            quote_spanned! {syn::spanned::Spanned::span(ty)=>
+                let _: &#ty = &m.#ident;
+                #column_check
+            }
+        })
+        .collect();
+
+    let info = ModelInfo {
+        prefix,
+        vis: input
+            .vis
+            .clone()
+            .unwrap_or_else(|| syn::Visibility::Public(Default::default())),
+        module: None,
+        entity: syn::parse_quote!(#module::Entity),
+        column: syn::parse_quote!(#module::Column),
+        manifest: None,
+        fields,
+        search: None,
+        tuples: FieldPerms::default(),
+    };
+    let builders = input
+        .sections
+        .iter()
+        .map(|(mode, _)| expand_mode(&info, *mode));
+    Ok(quote! {
+        const _: () = {
+            let _ = |m: &#module::Model| {
+                #(#type_checks)*
+            };
+        };
+        #(#builders)*
+    })
+}
@@ -4,10 +4,15 @@
 //! builder structs, methods and the Params snapshot types.
 
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
 use syn::DeriveInput;
 
-use crate::ast::{collect, to_camel, ModelInfoField};
+use crate::ast::{
+    col_partners, collect, to_camel, FieldPerms, JsonPath, ModelInfo, ModelInfoField, Op, OpShape,
+    Search, TupleIn, WhereOp,
+};
+use crate::types::{generic_arg, support, Marker, Support, TypeClass};
 
 /// Which builder kind to generate.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -17,93 +22,217 @@ pub enum Mode {
     Delete,
 }
 
+impl Mode {
+    pub const ALL: &'static [Mode] = &[Mode::Select, Mode::Update, Mode::Delete];
+
+    /// Spelling in attributes and `builders!`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Mode::Select => "select",
+            Mode::Update => "update",
+            Mode::Delete => "delete",
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Mode::Select => "Select",
+            Mode::Update => "Update",
+            Mode::Delete => "Delete",
+        }
+    }
+}
+
 /// Entry point used by the proc-macro functions in lib.rs
 pub fn expand(input: TokenStream, mode: Mode) -> TokenStream {
     let di = syn::parse_macro_input!(input as DeriveInput);
-    let (entity_prefix, fields) = match collect(&di) {
+    let info = match collect(&di) {
         Ok(v) => v,
         Err(err) => return err.to_compile_error().into(),
     };
+    expand_mode(&info, mode).into()
+}
 
-    let name_prefix = format_ident!("{}", entity_prefix);
+/// One builder (plus its Params type) for `info`; shared by the derives and
+/// `builders!`.
+pub fn expand_mode(info: &ModelInfo, mode: Mode) -> proc_macro2::TokenStream {
+    // Inside a generated module the types are `pub` and the module carries
+    // the configured visibility instead.
+    let vis = match info.module {
+        Some(_) => syn::Visibility::Public(Default::default()),
+        None => info.vis.clone(),
+    };
+    let paths = match info.module {
+        Some(_) => Paths {
+            entity: reanchor(&info.entity),
+            column: reanchor(&info.column),
+        },
+        None => Paths {
+            entity: info.entity.clone(),
+            column: info.column.clone(),
+        },
+    };
+    let name = format_ident!("{}{}", info.prefix, mode.suffix());
     let (builder_struct, builder_impl) = match mode {
-        Mode::Select => {
-            let name = format_ident!("{}Select", name_prefix);
-            build_select(&name, &fields)
-        }
-        Mode::Update => {
-            let name = format_ident!("{}Update", name_prefix);
-            build_update(&name, &fields)
-        }
-        Mode::Delete => {
-            let name = format_ident!("{}Delete", name_prefix);
-            build_delete(&name, &fields)
-        }
+        Mode::Select => build_select(
+            &name,
+            &vis,
+            &paths,
+            &info.fields,
+            &info.tuples,
+            info.search.as_ref(),
+        ),
+        Mode::Update => build_update(&name, &vis, &paths, &info.fields, &info.tuples),
+        Mode::Delete => build_delete(&name, &vis, &paths, &info.fields, &info.tuples),
     };
 
+    // rebuild when the manifest changes
+    let track = info.manifest.as_ref().map(|path| {
+        quote! { const _: &[u8] = ::core::include_bytes!(#path); }
+    });
     let out = quote! {
         #builder_struct
         #builder_impl
+        #track
     };
-    out.into()
+    match &info.module {
+        // Each derive expands on its own and a module can only be defined
+        // once, so every builder goes in a private module of its own and the
+        // derive of the first listed builder defines `module`, re-exporting
+        // them all.
+        Some(module) => {
+            let Some(at) = module.builders.iter().position(|b| b == mode.key()) else {
+                let msg = format!(
+                    "`module` gathers the listed builders only; add `{}` to `module(..)`",
+                    mode.key()
+                );
+                return quote_spanned! {module.name.span()=> ::core::compile_error!(#msg); };
+            };
+            let name = &module.name;
+            let inner = format_ident!("__{}_{}", name, mode.key());
+            let gather = (at == 0).then(|| {
+                let inners = module
+                    .builders
+                    .iter()
+                    .map(|b| format_ident!("__{}_{}", name, b));
+                let module_vis = &info.vis;
+                quote! {
+                    #module_vis mod #name {
+                        #(pub use super::#inners::*;)*
+                    }
+                }
+            });
+            quote! {
+                #[doc(hidden)]
+                mod #inner {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    #out
+                }
+                #gather
+            }
+        }
+        None => out,
+    }
+}
+
+/// Where the generated code finds the SeaORM entity, as seen from the builder.
+pub struct Paths {
+    pub entity: syn::Path,
+    pub column: syn::Path,
+}
+
+/// Adjust a path written relative to the model's module for use one module
+/// deeper: `self::X` becomes `super::X`, `super::X` becomes `super::super::X`.
+fn reanchor(path: &syn::Path) -> syn::Path {
+    match path.segments.first() {
+        Some(first) if path.leading_colon.is_none() && first.ident == "self" => {
+            let mut path = path.clone();
+            path.segments[0].ident = format_ident!("super");
+            path
+        }
+        Some(first) if path.leading_colon.is_none() && first.ident == "super" => {
+            let mut path = path.clone();
+            path.segments.insert(0, syn::parse_quote!(super));
+            path
+        }
+        _ => path.clone(),
+    }
 }
 
 pub fn build_select(
     name: &syn::Ident,
+    vis: &syn::Visibility,
+    paths: &Paths,
     fields: &Vec<ModelInfoField>,
+    tuples: &FieldPerms,
+    search: Option<&Search>,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    let mut methods = vec![];
-    let mut storages = vec![];
-    let mut inits = vec![];
-    let mut accessors = vec![];
-    let mut params_accessors = vec![];
-    let mut move_fields = vec![];
-    for f in fields {
-        for op in &f.perms.select_where {
-            let op_str = op.as_str();
-            let (s, i, m, a) = gen_where_pieces(&f.ident, &f.ty, op_str);
-            storages.push(s);
-            inits.push(i);
-            methods.push(m);
-            accessors.push(a);
-            let field_name = f.ident.to_string();
-            let storage_ident = format_ident!("{}_{}_val", field_name, op_str);
-            move_fields.push(quote! { #storage_ident: self.#storage_ident });
-            params_accessors.push(gen_params_accessors(&f.ident, &f.ty, op_str));
-        }
+    let Paths { entity, column } = paths;
+    let mut pieces = collect_where(
+        name,
+        vis,
+        fields,
+        &tuples.select_tuples,
+        paths,
+        |p| &p.select_where,
+        |p| &p.select_json,
+    );
+    if let Some(search) = search {
+        let (storage, init, method, accessor) = gen_search(search, column);
+        pieces.storages.push(storage);
+        pieces.inits.push(init);
+        pieces.methods.push(method);
+        pieces.accessors.push(accessor);
+        pieces
+            .move_fields
+            .push(quote! { search_val: self.search_val });
     }
+    let WherePieces {
+        storages,
+        inits,
+        methods,
+        accessors,
+        move_fields,
+        checks,
+        types,
+        clock,
+    } = pieces;
+    let (clock_field, clock_init, clock_method) = clock_pieces(clock);
     let st = quote! {
-        pub struct #name {
-            pub statement: ::sea_orm_builder::gen::Select<Entity>,
+        #vis struct #name {
+            pub statement: ::sea_orm_builder::gen::Select<#entity>,
             has_where: bool,
             where_params: ::std::vec::Vec<::sea_orm_builder::WhereParam>,
             #(#storages,)*
+            #clock_field
         }
     };
     let params_name = format_ident!("{}Params", name);
     let params_struct = quote! {
-        pub struct #params_name {
+        #vis struct #params_name {
             pub where_params: ::std::vec::Vec<::sea_orm_builder::WhereParam>,
             #(#storages,)*
         }
         impl #params_name {
-            #(#params_accessors)*
+            #(#accessors)*
             pub fn where_params(&self) -> &[::sea_orm_builder::WhereParam] { &self.where_params }
         }
     };
     let imp = quote! {
         impl #name {
-            pub fn new() -> Self { Self { statement: Entity::find(), has_where: false, where_params: ::std::vec::Vec::new(), #(#inits,)* } }
-            pub fn order_by_asc(mut self, col: Column) -> Self {
-                self.statement = <::sea_orm_builder::gen::Select<Entity> as ::sea_orm_builder::gen::QueryOrder>::order_by(
+            pub fn new() -> Self { Self { statement: <#entity as ::sea_orm_builder::gen::EntityTrait>::find(), has_where: false, where_params: ::std::vec::Vec::new(), #(#inits,)* #clock_init } }
+            #clock_method
+            pub fn order_by_asc(mut self, col: #column) -> Self {
+                self.statement = <::sea_orm_builder::gen::Select<#entity> as ::sea_orm_builder::gen::QueryOrder>::order_by(
                     self.statement,
                     col,
                     ::sea_orm_builder::gen::Order::Asc,
                 );
                 self
             }
-            pub fn order_by_desc(mut self, col: Column) -> Self {
-                self.statement = <::sea_orm_builder::gen::Select<Entity> as ::sea_orm_builder::gen::QueryOrder>::order_by(
+            pub fn order_by_desc(mut self, col: #column) -> Self {
+                self.statement = <::sea_orm_builder::gen::Select<#entity> as ::sea_orm_builder::gen::QueryOrder>::order_by(
                     self.statement,
                     col,
                     ::sea_orm_builder::gen::Order::Desc,
@@ -111,14 +240,14 @@ pub fn build_select(
                 self
             }
             pub fn limit(mut self, limit: u64) -> Self {
-                self.statement = <::sea_orm_builder::gen::Select<Entity> as ::sea_orm_builder::gen::QuerySelect>::limit(
+                self.statement = <::sea_orm_builder::gen::Select<#entity> as ::sea_orm_builder::gen::QuerySelect>::limit(
                     self.statement,
                     limit,
                 );
                 self
             }
             pub fn offset(mut self, offset: u64) -> Self {
-                self.statement = <::sea_orm_builder::gen::Select<Entity> as ::sea_orm_builder::gen::QuerySelect>::offset(
+                self.statement = <::sea_orm_builder::gen::Select<#entity> as ::sea_orm_builder::gen::QuerySelect>::offset(
                     self.statement,
                     offset,
                 );
@@ -126,77 +255,88 @@ pub fn build_select(
             }
             #(#methods)*
             #(#accessors)*
-            pub fn build(self) -> ::sea_orm_builder::gen::Select<Entity> { self.statement }
-            pub fn build_with_params(self) -> (::sea_orm_builder::gen::Select<Entity>, #params_name) {
+            pub fn build(self) -> ::sea_orm_builder::gen::Select<#entity> { self.statement }
+            pub fn build_with_params(self) -> (::sea_orm_builder::gen::Select<#entity>, #params_name) {
                 let p = #params_name { where_params: self.where_params, #(#move_fields,)* };
                 (self.statement, p)
             }
             pub fn where_params(&self) -> &[::sea_orm_builder::WhereParam] { &self.where_params }
         }
+        impl ::sea_orm_builder::IntoSubquery<<<#entity as ::sea_orm_builder::gen::EntityTrait>::PrimaryKey as ::sea_orm_builder::gen::PrimaryKeyTrait>::ValueType> for #name {
+            fn into_subquery(self) -> (::sea_orm_builder::gen::SelectStatement, ::std::vec::Vec<::sea_orm_builder::WhereParam>) {
+                (::sea_orm_builder::gen::pk_subquery(self.statement), self.where_params)
+            }
+        }
     };
-    (quote! { #st #params_struct }, imp)
+    (quote! { #st #params_struct #(#types)* #(#checks)* }, imp)
 }
 
 pub fn build_update(
     name: &syn::Ident,
+    vis: &syn::Visibility,
+    paths: &Paths,
     fields: &Vec<ModelInfoField>,
+    tuples: &FieldPerms,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    let mut where_methods = vec![];
-    let mut set_methods = vec![];
-    let mut storages = vec![];
-    let mut inits = vec![];
-    let mut accessors = vec![];
-    let mut params_accessors = vec![];
-    let mut move_fields = vec![];
-    for f in fields {
-        for op in &f.perms.update_where {
-            let op_str = op.as_str();
-            let (s, i, m, a) = gen_where_pieces(&f.ident, &f.ty, op_str);
-            storages.push(s);
-            inits.push(i);
-            where_methods.push(m);
-            accessors.push(a);
-            let field_name = f.ident.to_string();
-            let storage_ident = format_ident!("{}_{}_val", field_name, op_str);
-            move_fields.push(quote! { #storage_ident: self.#storage_ident });
-            params_accessors.push(gen_params_accessors(&f.ident, &f.ty, op_str));
-        }
-        if f.perms.update_set {
-            set_methods.push(gen_set_method(&f.ident, &f.ty));
-        }
-    }
+    let Paths { entity, column } = paths;
+    let WherePieces {
+        storages,
+        inits,
+        methods: where_methods,
+        accessors,
+        move_fields,
+        checks,
+        types,
+        clock,
+    } = collect_where(
+        name,
+        vis,
+        fields,
+        &tuples.update_tuples,
+        paths,
+        |p| &p.update_where,
+        |p| &p.update_json,
+    );
+    let (clock_field, clock_init, clock_method) = clock_pieces(clock);
+    let set_methods: Vec<_> = fields
+        .iter()
+        .filter(|f| f.perms.update_set)
+        .map(|f| gen_set_method(f, column))
+        .collect();
     let st = quote! {
-        pub struct #name {
-            pub statement: ::sea_orm_builder::gen::UpdateMany<Entity>,
+        #vis struct #name {
+            pub statement: ::sea_orm_builder::gen::UpdateMany<#entity>,
             has_where: bool,
             set_count: usize,
             where_params: ::std::vec::Vec<::sea_orm_builder::WhereParam>,
             #(#storages,)*
+            #clock_field
         }
     };
     let params_name = format_ident!("{}Params", name);
     let params_struct = quote! {
-        pub struct #params_name {
+        #vis struct #params_name {
             pub where_params: ::std::vec::Vec<::sea_orm_builder::WhereParam>,
             #(#storages,)*
         }
         impl #params_name {
-            #(#params_accessors)*
+            #(#accessors)*
             pub fn where_params(&self) -> &[::sea_orm_builder::WhereParam] { &self.where_params }
         }
     };
     let imp = quote! {
         impl #name {
-            pub fn new() -> Self { Self { statement: Entity::update_many(), has_where: false, set_count: 0, where_params: ::std::vec::Vec::new(), #(#inits,)* } }
+            pub fn new() -> Self { Self { statement: <#entity as ::sea_orm_builder::gen::EntityTrait>::update_many(), has_where: false, set_count: 0, where_params: ::std::vec::Vec::new(), #(#inits,)* #clock_init } }
+            #clock_method
             #(#set_methods)*
             #(#where_methods)*
             #(#accessors)*
-            pub fn build(self) -> Result<::sea_orm_builder::gen::UpdateMany<Entity>, ::sea_orm_builder::SeaOrmBuilderError> {
+            pub fn build(self) -> Result<::sea_orm_builder::gen::UpdateMany<#entity>, ::sea_orm_builder::SeaOrmBuilderError> {
                 if self.set_count == 0 { return Err(::sea_orm_builder::SeaOrmBuilderError::NoSet); }
                 if !self.has_where { return Err(::sea_orm_builder::SeaOrmBuilderError::NoWhere); }
                 Ok(self.statement)
             }
-            pub fn build_with_params(self) -> Result<(::sea_orm_builder::gen::UpdateMany<Entity>, #params_name), ::sea_orm_builder::SeaOrmBuilderError> {
+            pub fn build_with_params(self) -> Result<(::sea_orm_builder::gen::UpdateMany<#entity>, #params_name), ::sea_orm_builder::SeaOrmBuilderError> {
                 if self.set_count == 0 { return Err(::sea_orm_builder::SeaOrmBuilderError::NoSet); }
                 if !self.has_where { return Err(::sea_orm_builder::SeaOrmBuilderError::NoWhere); }
                 let p = #params_name { where_params: self.where_params, #(#move_fields,)* };
@@ -205,62 +345,67 @@ pub fn build_update(
             pub fn where_params(&self) -> &[::sea_orm_builder::WhereParam] { &self.where_params }
         }
     };
-    (quote! { #st #params_struct }, imp)
+    (quote! { #st #params_struct #(#types)* #(#checks)* }, imp)
 }
 
 pub fn build_delete(
     name: &syn::Ident,
+    vis: &syn::Visibility,
+    paths: &Paths,
     fields: &Vec<ModelInfoField>,
+    tuples: &FieldPerms,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    let mut where_methods = vec![];
-    let mut storages = vec![];
-    let mut inits = vec![];
-    let mut accessors = vec![];
-    let mut params_accessors = vec![];
-    let mut move_fields = vec![];
-    for f in fields {
-        for op in &f.perms.delete_where {
-            let op_str = op.as_str();
-            let (s, i, m, a) = gen_where_pieces(&f.ident, &f.ty, op_str);
-            storages.push(s);
-            inits.push(i);
-            where_methods.push(m);
-            accessors.push(a);
-            let field_name = f.ident.to_string();
-            let storage_ident = format_ident!("{}_{}_val", field_name, op_str);
-            move_fields.push(quote! { #storage_ident: self.#storage_ident });
-            params_accessors.push(gen_params_accessors(&f.ident, &f.ty, op_str));
-        }
-    }
+    let Paths { entity, .. } = paths;
+    let WherePieces {
+        storages,
+        inits,
+        methods: where_methods,
+        accessors,
+        move_fields,
+        checks,
+        types,
+        clock,
+    } = collect_where(
+        name,
+        vis,
+        fields,
+        &tuples.delete_tuples,
+        paths,
+        |p| &p.delete_where,
+        |p| &p.delete_json,
+    );
+    let (clock_field, clock_init, clock_method) = clock_pieces(clock);
     let st = quote! {
-        pub struct #name {
-            pub statement: ::sea_orm_builder::gen::DeleteMany<Entity>,
+        #vis struct #name {
+            pub statement: ::sea_orm_builder::gen::DeleteMany<#entity>,
             has_where: bool,
             where_params: ::std::vec::Vec<::sea_orm_builder::WhereParam>,
             #(#storages,)*
+            #clock_field
         }
     };
     let params_name = format_ident!("{}Params", name);
     let params_struct = quote! {
-        pub struct #params_name {
+        #vis struct #params_name {
             pub where_params: ::std::vec::Vec<::sea_orm_builder::WhereParam>,
             #(#storages,)*
         }
         impl #params_name {
-            #(#params_accessors)*
+            #(#accessors)*
             pub fn where_params(&self) -> &[::sea_orm_builder::WhereParam] { &self.where_params }
         }
     };
     let imp = quote! {
         impl #name {
-            pub fn new() -> Self { Self { statement: Entity::delete_many(), has_where: false, where_params: ::std::vec::Vec::new(), #(#inits,)* } }
+            pub fn new() -> Self { Self { statement: <#entity as ::sea_orm_builder::gen::EntityTrait>::delete_many(), has_where: false, where_params: ::std::vec::Vec::new(), #(#inits,)* #clock_init } }
+            #clock_method
             #(#where_methods)*
             #(#accessors)*
-            pub fn build(self) -> Result<::sea_orm_builder::gen::DeleteMany<Entity>, ::sea_orm_builder::SeaOrmBuilderError> {
+            pub fn build(self) -> Result<::sea_orm_builder::gen::DeleteMany<#entity>, ::sea_orm_builder::SeaOrmBuilderError> {
                 if !self.has_where { return Err(::sea_orm_builder::SeaOrmBuilderError::NoWhere); }
                 Ok(self.statement)
             }
-            pub fn build_with_params(self) -> Result<(::sea_orm_builder::gen::DeleteMany<Entity>, #params_name), ::sea_orm_builder::SeaOrmBuilderError> {
+            pub fn build_with_params(self) -> Result<(::sea_orm_builder::gen::DeleteMany<#entity>, #params_name), ::sea_orm_builder::SeaOrmBuilderError> {
                 if !self.has_where { return Err(::sea_orm_builder::SeaOrmBuilderError::NoWhere); }
                 let p = #params_name { where_params: self.where_params, #(#move_fields,)* };
                 Ok((self.statement, p))
@@ -268,85 +413,245 @@ pub fn build_delete(
             pub fn where_params(&self) -> &[::sea_orm_builder::WhereParam] { &self.where_params }
         }
     };
-    (quote! { #st #params_struct }, imp)
+    (quote! { #st #params_struct #(#types)* #(#checks)* }, imp)
 }
 
-fn gen_where_pieces(
-    field_ident: &syn::Ident,
-    field_ty: &syn::Type,
-    op: &str,
-) -> (
+/// Storage field, its init, the method and its accessors for one WHERE method.
+type MethodPieces = (
     proc_macro2::TokenStream,
     proc_macro2::TokenStream,
     proc_macro2::TokenStream,
     proc_macro2::TokenStream,
-) {
-    let field_name = field_ident.to_string();
-    let method_ident = match op {
-        "in" => format_ident!("{}_in", field_name),
-        o => format_ident!("{}_{}", field_name, o),
+);
+
+/// WHERE-related tokens shared by the three builders and their Params types.
+struct WherePieces {
+    storages: Vec<proc_macro2::TokenStream>,
+    inits: Vec<proc_macro2::TokenStream>,
+    methods: Vec<proc_macro2::TokenStream>,
+    /// `is_/get_` accessors; identical on the builder and its Params snapshot.
+    accessors: Vec<proc_macro2::TokenStream>,
+    move_fields: Vec<proc_macro2::TokenStream>,
+    /// Trait-bound checks for ops on types `types::classify` didn't recognise.
+    checks: Vec<proc_macro2::TokenStream>,
+    /// Types the methods take, emitted next to the builder.
+    types: Vec<proc_macro2::TokenStream>,
+    /// Whether a method reads the builder's clock (`within_last`).
+    clock: bool,
+}
+
+fn collect_where(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    fields: &[ModelInfoField],
+    tuples: &[TupleIn],
+    paths: &Paths,
+    ops: impl Fn(&FieldPerms) -> &Vec<WhereOp>,
+    json: impl Fn(&FieldPerms) -> &Vec<JsonPath>,
+) -> WherePieces {
+    let mut pieces = WherePieces {
+        storages: vec![],
+        inits: vec![],
+        methods: vec![],
+        accessors: vec![],
+        move_fields: vec![],
+        checks: vec![],
+        types: vec![],
+        clock: false,
     };
-    let column_variant = format_ident!("{}", to_camel(&field_name));
-    let storage_ident = format_ident!("{}_{}_val", field_name, op);
+    for f in fields {
+        // one enum of the columns `f` can be compared with, for its `<op>_col` methods
+        let partners = format_ident!("{}{}Col", name, to_camel(&f.name.to_string()));
+        if ops(&f.perms).iter().any(|w| w.op.col_base().is_some()) {
+            let others = col_partners(f, fields);
+            pieces
+                .types
+                .push(col_enum(&partners, vis, &others, &paths.column));
+        }
+        for w in ops(&f.perms) {
+            let op = w.op;
+            if let Support::Bound(marker) = support(op, &f.field_type) {
+                pieces.checks.push(gen_type_check(f, w, marker));
+            }
+            pieces.clock |= op == Op::WithinLast;
+            let method = match op.col_base() {
+                Some(_) => {
+                    col_cmp_pieces(f, &partners, &col_partners(f, fields), &paths.column, op)
+                }
+                None => gen_where_pieces(f, &paths.column, op),
+            };
+            pieces.push(&f.name, op, method);
+        }
+        for path in json(&f.perms) {
+            let prefix = format!("{}_{}", f.name, path.key);
+            for w in &path.ops {
+                let pieces_for = gen_json_pieces(f, path, &paths.column, w.op);
+                pieces.push(&prefix, w.op, pieces_for);
+            }
+        }
+    }
+    for tuple in tuples {
+        let members: Vec<&ModelInfoField> = tuple
+            .fields
+            .iter()
+            .map(|ident| {
+                let name = ident.unraw();
+                fields
+                    .iter()
+                    .find(|f| f.ident.unraw() == name)
+                    .expect("checked by ast::check_tuples")
+            })
+            .collect();
+        let prefix = members
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>()
+            .join("_");
+        pieces.push(
+            &prefix,
+            Op::In,
+            tuple_pieces(&prefix, &members, &paths.column),
+        );
+    }
+    pieces
+}
 
-    match op {
-        "eq" | "ne" | "lt" | "lte" | "gt" | "gte" | "like" | "ilike" => {
-            let op_ident = format_ident!("{}", op);
-            let storage = quote! { #storage_ident: ::std::option::Option<#field_ty> };
-            let init = quote! { #storage_ident: ::std::option::Option::None };
-            let is_ident = format_ident!("is_{}_{}", field_name, op);
-            let get_ident = format_ident!("get_{}_{}", field_name, op);
+impl WherePieces {
+    /// Add the pieces of the `<prefix>_<op>` method.
+    fn push(&mut self, prefix: &str, op: Op, (storage, init, method, accessor): MethodPieces) {
+        self.storages.push(storage);
+        self.inits.push(init);
+        self.methods.push(method);
+        self.accessors.push(accessor);
+        let storage_ident = format_ident!("{}_{}_val", prefix, op.name());
+        self.move_fields
+            .push(quote! { #storage_ident: self.#storage_ident });
+    }
+}
+
+fn gen_where_pieces(f: &ModelInfoField, column_path: &syn::Path, op: Op) -> MethodPieces {
+    if matches!(op, Op::On | Op::WithinLast) {
+        return temporal_pieces(f, column_path, op);
+    }
+    if matches!(op, Op::InSelect | Op::NotInSelect) {
+        return subquery_pieces(f, column_path, op);
+    }
+    // nullable fields are filtered by their inner `T`, except by
+    // `eq_null_safe`, which matches `None` too; array ops take elements
+    let element = match (f.field_type.class, op.array_shape()) {
+        (TypeClass::Array, Some(shape)) => {
+            generic_arg(&f.field_type.inner, "Vec").map(|t| (shape, t))
+        }
+        _ => None,
+    };
+    let (shape, field_ty) = match element {
+        Some((shape, element)) => (shape, element),
+        None if op == Op::EqNullSafe => (op.shape(), &f.ty),
+        None => (op.shape(), &f.field_type.inner),
+    };
+    let column = {
+        let column_variant = &f.column;
+        quote! { #column_path::#column_variant }
+    };
+    let filter_for = |args: &[proc_macro2::TokenStream]| match element {
+        Some(_) => array_filter(op, &column, &args[0]),
+        None => op_filter(op, &column, args),
+    };
+    let field_name = f.ident.unraw().to_string();
+    method_pieces(&f.name, &field_name, op, shape, field_ty, filter_for)
+}
+
+/// `<field>_<key>_<op>` for a key inside a JSON field; `WhereParam::field` is
+/// `<field>.<key>`.
+fn gen_json_pieces(
+    f: &ModelInfoField,
+    path: &JsonPath,
+    column_path: &syn::Path,
+    op: Op,
+) -> MethodPieces {
+    let column_variant = &f.column;
+    let key = &path.key;
+    let extract_as = match path.field_type.class {
+        TypeClass::Numeric => quote! { Number },
+        TypeClass::Boolean => quote! { Bool },
+        _ => quote! { Text },
+    };
+    let value = quote! {
+        ::sea_orm_builder::gen::json_field(#column_path::#column_variant, #key, ::sea_orm_builder::gen::JsonAs::#extract_as)
+    };
+    let filter_for = |args: &[proc_macro2::TokenStream]| json_filter(op, &value, args);
+    let prefix = format!("{}_{}", f.name, key);
+    let field_name = format!("{}.{}", f.ident.unraw(), key);
+    let field_ty = &path.field_type.inner;
+    method_pieces(&prefix, &field_name, op, op.shape(), field_ty, filter_for)
+}
+
+/// Storage, init, method and accessors of the `<prefix>_<op>` WHERE method
+/// taking `field_ty` inputs; `filter_for` builds the filter from the inputs.
+fn method_pieces(
+    prefix: &str,
+    field_name: &str,
+    op: Op,
+    shape: OpShape,
+    field_ty: &syn::Type,
+    filter_for: impl Fn(&[proc_macro2::TokenStream]) -> proc_macro2::TokenStream,
+) -> MethodPieces {
+    let op_name = op.name();
+    let method_ident = format_ident!("{}_{}", prefix, op_name);
+    let storage_ident = format_ident!("{}_{}_val", prefix, op_name);
+    let is_ident = format_ident!("is_{}_{}", prefix, op_name);
+    let get_ident = format_ident!("get_{}_{}", prefix, op_name);
+    let init = quote! { #storage_ident: ::std::option::Option::None };
+
+    match shape {
+        OpShape::Flag => {
+            let filter = filter_for(&[]);
+            let storage = quote! { #storage_ident: bool };
+            let init = quote! { #storage_ident: false };
             let method = quote! {
-                pub fn #method_ident<V: ::sea_orm_builder::IntoField<#field_ty>>(mut self, v: V) -> Self where #field_ty: ::std::clone::Clone {
-                    let vv: #field_ty = v.into_field();
-                    self.#storage_ident = ::std::option::Option::Some(vv.clone());
-                    self.statement = self.statement.filter(Column::#column_variant.#op_ident(vv));
+                pub fn #method_ident(mut self) -> Self {
+                    self.#storage_ident = true;
+                    self.statement = ::sea_orm_builder::gen::QueryFilter::filter(self.statement, #filter);
                     self.has_where = true;
-                    self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op, value: ::sea_orm_builder::WhereValue::Single(format!("{:?}", &self.#storage_ident)) });
+                    self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::None });
                     self
                 }
             };
             let accessor = quote! {
-                pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
-                pub fn #get_ident(&self) -> ::std::option::Option<&#field_ty> { self.#storage_ident.as_ref() }
+                pub fn #is_ident(&self) -> bool { self.#storage_ident }
             };
             (storage, init, method, accessor)
         }
-        "in" => {
-            let storage =
-                quote! { #storage_ident: ::std::option::Option<::std::vec::Vec<#field_ty>> };
-            let init = quote! { #storage_ident: ::std::option::Option::None };
-            let is_ident = format_ident!("is_{}_in", field_name);
-            let get_ident = format_ident!("get_{}_in", field_name);
+        OpShape::Single => {
+            let filter = filter_for(&[quote! { vv }]);
+            let storage = quote! { #storage_ident: ::std::option::Option<#field_ty> };
             let method = quote! {
-                pub fn #method_ident<V: ::sea_orm_builder::IntoField<#field_ty>, I: IntoIterator<Item = V>>(mut self, iter: I) -> Self where #field_ty: ::std::clone::Clone {
-                    let vec_tmp: ::std::vec::Vec<#field_ty> = iter.into_iter().map(|x| x.into_field()).collect();
-                    self.#storage_ident = ::std::option::Option::Some(vec_tmp.clone());
-                    self.statement = self.statement.filter(Column::#column_variant.is_in(vec_tmp));
+                pub fn #method_ident<V: ::sea_orm_builder::IntoField<#field_ty>>(mut self, v: V) -> Self where #field_ty: ::std::clone::Clone {
+                    let vv: #field_ty = v.into_field();
+                    self.#storage_ident = ::std::option::Option::Some(vv.clone());
+                    self.statement = ::sea_orm_builder::gen::QueryFilter::filter(self.statement, #filter);
                     self.has_where = true;
-                    self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op, value: ::sea_orm_builder::WhereValue::List(self.#storage_ident.as_ref().unwrap().iter().map(|x| format!("{:?}", x)).collect()) });
+                    self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::Single(format!("{:?}", &self.#storage_ident)) });
                     self
                 }
             };
             let accessor = quote! {
                 pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
-                pub fn #get_ident(&self) -> ::std::option::Option<&[#field_ty]> { self.#storage_ident.as_deref().map(|v| &v[..]) }
+                pub fn #get_ident(&self) -> ::std::option::Option<&#field_ty> { self.#storage_ident.as_ref() }
             };
             (storage, init, method, accessor)
         }
-        "not_in" => {
+        OpShape::List => {
+            let filter = filter_for(&[quote! { vec_tmp }]);
             let storage =
                 quote! { #storage_ident: ::std::option::Option<::std::vec::Vec<#field_ty>> };
-            let init = quote! { #storage_ident: ::std::option::Option::None };
-            let is_ident = format_ident!("is_{}_not_in", field_name);
-            let get_ident = format_ident!("get_{}_not_in", field_name);
             let method = quote! {
                 pub fn #method_ident<V: ::sea_orm_builder::IntoField<#field_ty>, I: IntoIterator<Item = V>>(mut self, iter: I) -> Self where #field_ty: ::std::clone::Clone {
                     let vec_tmp: ::std::vec::Vec<#field_ty> = iter.into_iter().map(|x| x.into_field()).collect();
                     self.#storage_ident = ::std::option::Option::Some(vec_tmp.clone());
-                    self.statement = self.statement.filter(Column::#column_variant.is_not_in(vec_tmp));
+                    self.statement = ::sea_orm_builder::gen::QueryFilter::filter(self.statement, #filter);
                     self.has_where = true;
-                    self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op, value: ::sea_orm_builder::WhereValue::List(self.#storage_ident.as_ref().unwrap().iter().map(|x| format!("{:?}", x)).collect()) });
+                    self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::List(self.#storage_ident.as_ref().unwrap().iter().map(|x| format!("{:?}", x)).collect()) });
                     self
                 }
             };
@@ -356,20 +661,18 @@ fn gen_where_pieces(
             };
             (storage, init, method, accessor)
         }
-        "between" => {
+        OpShape::Range => {
+            let filter = filter_for(&[quote! { a }, quote! { b }]);
             let storage = quote! { #storage_ident: ::std::option::Option<(#field_ty, #field_ty)> };
-            let init = quote! { #storage_ident: ::std::option::Option::None };
-            let is_ident = format_ident!("is_{}_between", field_name);
-            let get_ident = format_ident!("get_{}_between", field_name);
             let method = quote! {
                 pub fn #method_ident<V1: ::sea_orm_builder::IntoField<#field_ty>, V2: ::sea_orm_builder::IntoField<#field_ty>>(mut self, a: V1, b: V2) -> Self where #field_ty: ::std::clone::Clone {
                     let a: #field_ty = a.into_field();
                     let b: #field_ty = b.into_field();
                     self.#storage_ident = ::std::option::Option::Some((a.clone(), b.clone()));
-                    self.statement = self.statement.filter(Column::#column_variant.between(a, b));
+                    self.statement = ::sea_orm_builder::gen::QueryFilter::filter(self.statement, #filter);
                     self.has_where = true;
                     if let ::std::option::Option::Some((ref sa, ref sb)) = self.#storage_ident {
-                        self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op, value: ::sea_orm_builder::WhereValue::Range { start: format!("{:?}", sa), end: format!("{:?}", sb) } });
+                        self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::Range { start: format!("{:?}", sa), end: format!("{:?}", sb) } });
                     }
                     self
                 }
@@ -380,71 +683,506 @@ fn gen_where_pieces(
             };
             (storage, init, method, accessor)
         }
-        _ => {
-            let msg = format!("unsupported op: {}", op);
-            (
-                quote! {},
-                quote! {},
-                quote! { const _: () = { compile_error!(#msg); }; },
-                quote! {},
-            )
-        }
     }
 }
 
-fn gen_set_method(field_ident: &syn::Ident, field_ty: &syn::Type) -> proc_macro2::TokenStream {
-    let method_ident = format_ident!("set_{}", field_ident);
-    let column_variant = format_ident!("{}", to_camel(&field_ident.to_string()));
+/// The builder's clock field, its init and `with_clock`, for builders with
+/// `within_last`; empty otherwise.
+fn clock_pieces(
+    clock: bool,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    if !clock {
+        return Default::default();
+    }
+    (
+        quote! { clock: ::std::sync::Arc<dyn ::sea_orm_builder::Clock>, },
+        quote! { clock: ::std::sync::Arc::new(::sea_orm_builder::SystemClock), },
+        quote! {
+            pub fn with_clock<C: ::sea_orm_builder::Clock + 'static>(mut self, clock: C) -> Self {
+                self.clock = ::std::sync::Arc::new(clock);
+                self
+            }
+        },
+    )
+}
+
+/// `<field>_on(date)` and `<field>_within_last(duration)`, which resolve
+/// their input to a range; `WhereParam` records the resolved range.
+fn temporal_pieces(f: &ModelInfoField, column_path: &syn::Path, op: Op) -> MethodPieces {
+    let op_name = op.name();
+    let method_ident = format_ident!("{}_{}", f.name, op_name);
+    let storage_ident = format_ident!("{}_{}_val", f.name, op_name);
+    let is_ident = format_ident!("is_{}_{}", f.name, op_name);
+    let get_ident = format_ident!("get_{}_{}", f.name, op_name);
+    let field_name = f.ident.unraw().to_string();
+    let column_variant = &f.column;
+    let column = quote! { #column_path::#column_variant };
+    let ty = &f.field_type.inner;
+    let col_trait = quote! { ::sea_orm_builder::gen::ColumnTrait };
+    let (input_ty, range, filter) = match op {
+        Op::On => (
+            quote! { ::sea_orm_builder::gen::Date },
+            quote! { <#ty as ::sea_orm_builder::gen::Temporal>::day(v) },
+            quote! {
+                ::sea_orm_builder::gen::ExprTrait::and(
+                    #col_trait::gte(&#column, start.clone()),
+                    #col_trait::lt(&#column, end.clone()),
+                )
+            },
+        ),
+        Op::WithinLast => (
+            quote! { ::std::time::Duration },
+            quote! { ::sea_orm_builder::gen::last::<#ty>(&*self.clock, v) },
+            quote! { #col_trait::between(&#column, start.clone(), end.clone()) },
+        ),
+        _ => unreachable!("`{}` takes its input as is", op_name),
+    };
+    let storage = quote! { #storage_ident: ::std::option::Option<#input_ty> };
+    let init = quote! { #storage_ident: ::std::option::Option::None };
+    let method = quote! {
+        pub fn #method_ident<V: ::sea_orm_builder::IntoField<#input_ty>>(mut self, v: V) -> Self {
+            let v: #input_ty = v.into_field();
+            self.#storage_ident = ::std::option::Option::Some(v);
+            let (start, end): (#ty, #ty) = #range;
+            self.statement = ::sea_orm_builder::gen::QueryFilter::filter(self.statement, #filter);
+            self.has_where = true;
+            self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::Range { start: format!("{:?}", start), end: format!("{:?}", end) } });
+            self
+        }
+    };
+    let accessor = quote! {
+        pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
+        pub fn #get_ident(&self) -> ::std::option::Option<&#input_ty> { self.#storage_ident.as_ref() }
+    };
+    (storage, init, method, accessor)
+}
+
+/// `<a>_<b>_in([(a, b), ..])` for a struct-level `tuple_in(a, b)`; each
+/// element goes through `IntoField` like a single-field `in`.
+fn tuple_pieces(
+    prefix: &str,
+    members: &[&ModelInfoField],
+    column_path: &syn::Path,
+) -> MethodPieces {
+    let op_name = Op::In.name();
+    let method_ident = format_ident!("{}_{}", prefix, op_name);
+    let storage_ident = format_ident!("{}_{}_val", prefix, op_name);
+    let is_ident = format_ident!("is_{}_{}", prefix, op_name);
+    let get_ident = format_ident!("get_{}_{}", prefix, op_name);
+    let field_name = members
+        .iter()
+        .map(|f| f.ident.unraw().to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let tys: Vec<&syn::Type> = members.iter().map(|f| &f.field_type.inner).collect();
+    let vs: Vec<syn::Ident> = (0..members.len())
+        .map(|i| format_ident!("V{}", i))
+        .collect();
+    let xs: Vec<syn::Ident> = (0..members.len())
+        .map(|i| format_ident!("x{}", i))
+        .collect();
+    let columns = members.iter().map(|f| &f.column);
+    let tuple_ty = quote! { (#(#tys,)*) };
+    let storage = quote! { #storage_ident: ::std::option::Option<::std::vec::Vec<#tuple_ty>> };
+    let init = quote! { #storage_ident: ::std::option::Option::None };
+    let method = quote! {
+        pub fn #method_ident<#(#vs: ::sea_orm_builder::IntoField<#tys>,)* I: IntoIterator<Item = (#(#vs,)*)>>(mut self, iter: I) -> Self {
+            let vec_tmp: ::std::vec::Vec<#tuple_ty> = iter.into_iter().map(|(#(#xs,)*)| (#(#xs.into_field(),)*)).collect();
+            self.#storage_ident = ::std::option::Option::Some(vec_tmp.clone());
+            self.statement = ::sea_orm_builder::gen::QueryFilter::filter(
+                self.statement,
+                ::sea_orm_builder::gen::tuple_in(&[#(#column_path::#columns),*], vec_tmp),
+            );
+            self.has_where = true;
+            self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::List(self.#storage_ident.as_ref().unwrap().iter().map(|x| format!("{:?}", x)).collect()) });
+            self
+        }
+    };
+    let accessor = quote! {
+        pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
+        pub fn #get_ident(&self) -> ::std::option::Option<&[#tuple_ty]> { self.#storage_ident.as_deref() }
+    };
+    (storage, init, method, accessor)
+}
+
+/// `<field>_in_select(sub)` / `<field>_not_in_select(sub)`; Params and
+/// `WhereParam` keep the subquery's own where params.
+fn subquery_pieces(f: &ModelInfoField, column_path: &syn::Path, op: Op) -> MethodPieces {
+    let op_name = op.name();
+    let method_ident = format_ident!("{}_{}", f.name, op_name);
+    let storage_ident = format_ident!("{}_{}_val", f.name, op_name);
+    let is_ident = format_ident!("is_{}_{}", f.name, op_name);
+    let get_ident = format_ident!("get_{}_{}", f.name, op_name);
+    let field_name = f.ident.unraw().to_string();
+    let column_variant = &f.column;
+    let field_ty = &f.field_type.inner;
+    let f_sub = match op {
+        Op::InSelect => format_ident!("in_subquery"),
+        _ => format_ident!("not_in_subquery"),
+    };
+    let storage = quote! { #storage_ident: ::std::option::Option<::std::vec::Vec<::sea_orm_builder::WhereParam>> };
+    let init = quote! { #storage_ident: ::std::option::Option::None };
+    let method = quote! {
+        pub fn #method_ident<S: ::sea_orm_builder::IntoSubquery<#field_ty>>(mut self, sub: S) -> Self {
+            let (query, params) = sub.into_subquery();
+            self.#storage_ident = ::std::option::Option::Some(params.clone());
+            self.statement = ::sea_orm_builder::gen::QueryFilter::filter(
+                self.statement,
+                ::sea_orm_builder::gen::ColumnTrait::#f_sub(&#column_path::#column_variant, query),
+            );
+            self.has_where = true;
+            self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::Nested(params) });
+            self
+        }
+    };
+    let accessor = quote! {
+        pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
+        pub fn #get_ident(&self) -> ::std::option::Option<&[::sea_orm_builder::WhereParam]> { self.#storage_ident.as_deref() }
+    };
+    (storage, init, method, accessor)
+}
+
+/// `<field>_<op>_col(other)`, comparing with the column `other` names out of
+/// the `partners` enum; `WhereParam` records that column's field name.
+fn col_cmp_pieces(
+    f: &ModelInfoField,
+    partners: &syn::Ident,
+    others: &[&ModelInfoField],
+    column_path: &syn::Path,
+    op: Op,
+) -> MethodPieces {
+    let op_name = op.name();
+    let method_ident = format_ident!("{}_{}", f.name, op_name);
+    let storage_ident = format_ident!("{}_{}_val", f.name, op_name);
+    let is_ident = format_ident!("is_{}_{}", f.name, op_name);
+    let get_ident = format_ident!("get_{}_{}", f.name, op_name);
+    let field_name = f.ident.unraw().to_string();
+    let column_variant = &f.column;
+    let base = format_ident!("{}", op.col_base().expect("column comparison").name());
+    let variants = others.iter().map(|g| &g.column);
+    let other_names = others.iter().map(|g| g.ident.unraw().to_string());
+    let storage = quote! { #storage_ident: ::std::option::Option<#column_path> };
+    let init = quote! { #storage_ident: ::std::option::Option::None };
+    let method = quote! {
+        pub fn #method_ident(mut self, other: #partners) -> Self {
+            let (other, other_name) = match other {
+                #(#partners::#variants => (#column_path::#variants, #other_names),)*
+            };
+            self.#storage_ident = ::std::option::Option::Some(other);
+            self.statement = ::sea_orm_builder::gen::QueryFilter::filter(
+                self.statement,
+                ::sea_orm_builder::gen::ExprTrait::#base(
+                    ::sea_orm_builder::gen::ColumnTrait::into_expr(#column_path::#column_variant),
+                    ::sea_orm_builder::gen::ColumnTrait::into_expr(other),
+                ),
+            );
+            self.has_where = true;
+            self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::Column(other_name.to_string()) });
+            self
+        }
+    };
+    let accessor = quote! {
+        pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
+        pub fn #get_ident(&self) -> ::std::option::Option<&#column_path> { self.#storage_ident.as_ref() }
+    };
+    (storage, init, method, accessor)
+}
+
+/// The `Column` variants of `others` as an enum of their own, so an
+/// `<op>_col` method only accepts columns of a compatible type.
+fn col_enum(
+    partners: &syn::Ident,
+    vis: &syn::Visibility,
+    others: &[&ModelInfoField],
+    column_path: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let variants: Vec<&syn::Ident> = others.iter().map(|g| &g.column).collect();
     quote! {
-        pub fn #method_ident<V: ::sea_orm_builder::IntoField<#field_ty>>(mut self, v: V) -> Self {
-            let v: #field_ty = v.into_field();
-            self.statement = self.statement.col_expr(Column::#column_variant, ::sea_orm_builder::gen::Expr::value(v));
-            self.set_count += 1;
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        #vis enum #partners { #(#variants,)* }
+        impl ::std::convert::From<#partners> for #column_path {
+            fn from(other: #partners) -> Self {
+                match other { #(#partners::#variants => #column_path::#variants,)* }
+            }
+        }
+    }
+}
+
+/// `search(q)`: full-text search over the struct-level `search` fields.
+fn gen_search(search: &Search, column_path: &syn::Path) -> MethodPieces {
+    let columns = &search.columns;
+    let field_name = search.names.join(",");
+    let op_name = Search::KEY;
+    let storage = quote! { search_val: ::std::option::Option<::std::string::String> };
+    let init = quote! { search_val: ::std::option::Option::None };
+    let method = quote! {
+        pub fn search<V: ::sea_orm_builder::IntoField<::std::string::String>>(mut self, q: V) -> Self {
+            let q: ::std::string::String = q.into_field();
+            self.search_val = ::std::option::Option::Some(q.clone());
+            self.statement = ::sea_orm_builder::gen::QueryFilter::filter(
+                self.statement,
+                ::sea_orm_builder::gen::full_text([#(#column_path::#columns),*], q),
+            );
+            self.has_where = true;
+            self.where_params.push(::sea_orm_builder::WhereParam { field: #field_name, op: #op_name, value: ::sea_orm_builder::WhereValue::Single(format!("{:?}", &self.search_val)) });
             self
         }
+    };
+    let accessor = quote! {
+        pub fn is_search(&self) -> bool { self.search_val.is_some() }
+        pub fn get_search(&self) -> ::std::option::Option<&::std::string::String> { self.search_val.as_ref() }
+    };
+    (storage, init, method, accessor)
+}
+
+/// Compile-time check that `f`'s type implements `marker`. Emitted with the
+/// op's attribute span so the error names the field and op and points there.
+fn gen_type_check(f: &ModelInfoField, w: &WhereOp, marker: Marker) -> proc_macro2::TokenStream {
+    let field_name = f.ident.unraw().to_string();
+    let op_name = w.op.name();
+    let check_trait = format_ident!("{}{}", to_camel(&f.name), to_camel(op_name));
+    let marker_ident = format_ident!("{}", marker.trait_name());
+    let message = format!(
+        "op `{}` on field `{}` needs {} type, found `{{Self}}`",
+        op_name,
+        field_name,
+        marker.article_name()
+    );
+    let label = format!("`{}` is not supported for `{{Self}}`", op_name);
+    let note = format!(
+        "implement `sea_orm_builder::kind::{}` for `{{Self}}` if it supports `{}`",
+        marker.trait_name(),
+        op_name
+    );
+    let inner = respan(
+        quote::ToTokens::to_token_stream(&f.field_type.inner),
+        w.span,
+    );
+    quote_spanned! {w.span=>
+        const _: () = {
+            #[diagnostic::on_unimplemented(message = #message, label = #label, note = #note)]
+            trait #check_trait {}
+            impl<T: ?::core::marker::Sized + ::sea_orm_builder::kind::#marker_ident> #check_trait for T {}
+            fn check<T: ?::core::marker::Sized + #check_trait>() {}
+            let _ = check::<#inner>;
+        };
     }
 }
 
-fn gen_params_accessors(
-    field_ident: &syn::Ident,
-    field_ty: &syn::Type,
-    op: &str,
+fn respan(tokens: proc_macro2::TokenStream, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            proc_macro2::TokenTree::Group(g) => {
+                let mut group = proc_macro2::Group::new(g.delimiter(), respan(g.stream(), span));
+                group.set_span(span);
+                proc_macro2::TokenTree::Group(group)
+            }
+            mut other => {
+                other.set_span(span);
+                other
+            }
+        })
+        .collect()
+}
+
+/// The filter expression for `op` on a value extracted from a JSON field.
+fn json_filter(
+    op: Op,
+    value: &proc_macro2::TokenStream,
+    args: &[proc_macro2::TokenStream],
 ) -> proc_macro2::TokenStream {
-    let field_name = field_ident.to_string();
-    let storage_ident = format_ident!("{}_{}_val", field_name, op);
+    let expr_trait = quote! { ::sea_orm_builder::gen::ExprTrait };
+    let f = match op {
+        Op::In => format_ident!("is_in"),
+        Op::NotIn => format_ident!("is_not_in"),
+        _ => format_ident!("{}", op.name()),
+    };
+    quote! { #expr_trait::#f(#value, #(#args),*) }
+}
+
+/// The filter expression for an op on a Postgres array field.
+fn array_filter(
+    op: Op,
+    column: &proc_macro2::TokenStream,
+    arg: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let f = match op {
+        Op::AnyEq => return quote! { ::sea_orm_builder::gen::array_any_eq(#column, #arg) },
+        Op::Contains => quote! { Contains },
+        Op::ContainedBy => quote! { ContainedBy },
+        Op::Overlaps => quote! { Overlaps },
+        _ => unreachable!("`{}` has no array form", op.name()),
+    };
+    quote! { ::sea_orm_builder::gen::array_op(#column, ::sea_orm_builder::gen::ArrayOp::#f, #arg) }
+}
+
+/// The filter expression for `op` applied to `column` with the given inputs.
+fn op_filter(
+    op: Op,
+    column: &proc_macro2::TokenStream,
+    args: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let col_trait = quote! { ::sea_orm_builder::gen::ColumnTrait };
     match op {
-        "eq" | "ne" | "lt" | "lte" | "gt" | "gte" | "like" | "ilike" => {
-            let is_ident = format_ident!("is_{}_{}", field_name, op);
-            let get_ident = format_ident!("get_{}_{}", field_name, op);
+        Op::Eq | Op::Ne | Op::Lt | Op::Lte | Op::Gt | Op::Gte | Op::Like | Op::NotLike => {
+            let f = format_ident!("{}", op.name());
+            let v = &args[0];
+            quote! { #col_trait::#f(&#column, #v) }
+        }
+        Op::Ilike | Op::NotIlike => {
+            let f = format_ident!("{}", op.name());
+            let v = &args[0];
+            quote! { ::sea_orm_builder::gen::PgExpr::#f(#col_trait::into_expr(#column), #v) }
+        }
+        Op::Ieq => {
+            let v = &args[0];
             quote! {
-                pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
-                pub fn #get_ident(&self) -> ::std::option::Option<&#field_ty> { self.#storage_ident.as_ref() }
+                ::sea_orm_builder::gen::eq_ignore_case(#column, ::std::convert::Into::<::std::string::String>::into(#v))
             }
         }
-        "in" => {
-            let is_ident = format_ident!("is_{}_in", field_name);
-            let get_ident = format_ident!("get_{}_in", field_name);
+        Op::Regex | Op::NotRegex => {
+            let v = &args[0];
+            let negate = op == Op::NotRegex;
             quote! {
-                pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
-                pub fn #get_ident(&self) -> ::std::option::Option<&[#field_ty]> { self.#storage_ident.as_deref().map(|v| &v[..]) }
+                ::sea_orm_builder::gen::regex_match(#column, ::std::convert::Into::<::std::string::String>::into(#v), #negate)
             }
         }
-        "not_in" => {
-            let is_ident = format_ident!("is_{}_not_in", field_name);
-            let get_ident = format_ident!("get_{}_not_in", field_name);
+        Op::Fts => {
+            let v = &args[0];
             quote! {
-                pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
-                pub fn #get_ident(&self) -> ::std::option::Option<&[#field_ty]> { self.#storage_ident.as_deref().map(|v| &v[..]) }
+                ::sea_orm_builder::gen::full_text([#column], ::std::convert::Into::<::std::string::String>::into(#v))
             }
         }
-        "between" => {
-            let is_ident = format_ident!("is_{}_between", field_name);
-            let get_ident = format_ident!("get_{}_between", field_name);
+        Op::StartsWith
+        | Op::EndsWith
+        | Op::Contains
+        | Op::IstartsWith
+        | Op::IendsWith
+        | Op::Icontains => {
+            let v = &args[0];
+            let leading = matches!(
+                op,
+                Op::EndsWith | Op::Contains | Op::IendsWith | Op::Icontains
+            );
+            let trailing = matches!(
+                op,
+                Op::StartsWith | Op::Contains | Op::IstartsWith | Op::Icontains
+            );
+            let ignore_case = matches!(op, Op::IstartsWith | Op::IendsWith | Op::Icontains);
             quote! {
-                pub fn #is_ident(&self) -> bool { self.#storage_ident.is_some() }
-                pub fn #get_ident(&self) -> ::std::option::Option<(&#field_ty, &#field_ty)> { self.#storage_ident.as_ref().map(|(a,b)| (a,b)) }
+                ::sea_orm_builder::gen::like_escaped(
+                    #column,
+                    ::sea_orm_builder::gen::like_pattern(&::std::convert::Into::<::std::string::String>::into(#v), #leading, #trailing),
+                    #ignore_case,
+                )
             }
         }
-        _ => quote! {},
+        Op::In => {
+            let v = &args[0];
+            quote! { #col_trait::is_in(&#column, #v) }
+        }
+        Op::NotIn => {
+            let v = &args[0];
+            quote! { #col_trait::is_not_in(&#column, #v) }
+        }
+        Op::Between | Op::NotBetween => {
+            let f = format_ident!("{}", op.name());
+            let (a, b) = (&args[0], &args[1]);
+            quote! { #col_trait::#f(&#column, #a, #b) }
+        }
+        Op::ContainedBy | Op::Overlaps | Op::AnyEq => {
+            unreachable!("`{}` is only generated for array fields", op.name())
+        }
+        Op::HasFlags | Op::HasAnyFlag | Op::LacksFlags => {
+            let f = match op {
+                Op::HasFlags => quote! { All },
+                Op::HasAnyFlag => quote! { Any },
+                _ => quote! { None },
+            };
+            let v = &args[0];
+            quote! { ::sea_orm_builder::gen::flags(#column, ::sea_orm_builder::gen::FlagOp::#f, #v) }
+        }
+        Op::Before => {
+            let v = &args[0];
+            quote! { #col_trait::lt(&#column, #v) }
+        }
+        Op::Since => {
+            let v = &args[0];
+            quote! { #col_trait::gte(&#column, #v) }
+        }
+        Op::On
+        | Op::WithinLast
+        | Op::InSelect
+        | Op::NotInSelect
+        | Op::EqCol
+        | Op::NeCol
+        | Op::LtCol
+        | Op::LteCol
+        | Op::GtCol
+        | Op::GteCol => unreachable!("`{}` has its own method", op.name()),
+        Op::IsNull => quote! { #col_trait::is_null(&#column) },
+        Op::IsNotNull => quote! { #col_trait::is_not_null(&#column) },
+        Op::EqNullSafe => {
+            let v = &args[0];
+            quote! { ::sea_orm_builder::gen::eq_null_safe(#column, #v) }
+        }
+    }
+}
+
+/// `set_<field>`, plus `set_<field>_null` for nullable fields and
+/// `set_<field>_add_flags`/`set_<field>_remove_flags` for fields with bitflag ops.
+fn gen_set_method(f: &ModelInfoField, column_path: &syn::Path) -> proc_macro2::TokenStream {
+    let field_ty = &f.ty;
+    let method_ident = format_ident!("set_{}", f.name);
+    let column_variant = &f.column;
+    let set = quote! {
+        pub fn #method_ident<V: ::sea_orm_builder::IntoField<#field_ty>>(mut self, v: V) -> Self {
+            let v: #field_ty = v.into_field();
+            self.statement = self.statement.col_expr(#column_path::#column_variant, ::sea_orm_builder::gen::Expr::value(v));
+            self.set_count += 1;
+            self
+        }
+    };
+    let inner = &f.field_type.inner;
+    let null = f.field_type.optional.then(|| {
+        let null_ident = format_ident!("set_{}_null", f.name);
+        quote! {
+            pub fn #null_ident(mut self) -> Self {
+                self.statement = self.statement.col_expr(#column_path::#column_variant, ::sea_orm_builder::gen::Expr::value(::std::option::Option::<#inner>::None));
+                self.set_count += 1;
+                self
+            }
+        }
+    });
+    // `col = col | mask` and `col = col & !mask`, with `!mask` computed in Rust
+    // since the bitwise NOT operator differs between backends
+    let flags = f.perms.all_where().any(|w| w.op.bitflag()).then(|| {
+        let add_ident = format_ident!("set_{}_add_flags", f.name);
+        let remove_ident = format_ident!("set_{}_remove_flags", f.name);
+        let col = quote! { ::sea_orm_builder::gen::Expr::col(#column_path::#column_variant) };
+        quote! {
+            pub fn #add_ident<V: ::sea_orm_builder::IntoField<#inner>>(mut self, mask: V) -> Self {
+                let mask: #inner = mask.into_field();
+                self.statement = self.statement.col_expr(#column_path::#column_variant, ::sea_orm_builder::gen::ExprTrait::bit_or(#col, mask));
+                self.set_count += 1;
+                self
+            }
+            pub fn #remove_ident<V: ::sea_orm_builder::IntoField<#inner>>(mut self, mask: V) -> Self {
+                let mask: #inner = mask.into_field();
+                self.statement = self.statement.col_expr(#column_path::#column_variant, ::sea_orm_builder::gen::ExprTrait::bit_and(#col, !mask));
+                self.set_count += 1;
+                self
+            }
+        }
+    });
+    quote! {
+        #set
+        #null
+        #flags
     }
 }
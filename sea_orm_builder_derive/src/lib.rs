@@ -2,12 +2,18 @@
 //!
 //! Thin, well-documented proc-macro entry points delegating to
 //! - `ast`: parsing SeaORM Model + `#[sea_builder(..)]` attributes
+//! - `manifest`: the optional `sea_builder.toml` permission manifest
+//! - `types`: classifying field types to validate ops against them
 //! - `gen`: code generation for Select/Update/Delete builders
+//! - `decl`: parsing `builders!` declarations for entities that can't be annotated
 //!
 //! Keeping `lib.rs` small makes the crate easier to read and maintain.
 
 mod ast;
+mod decl;
 mod gen;
+mod manifest;
+mod types;
 
 use proc_macro::TokenStream;
 
@@ -28,3 +34,18 @@ pub fn derive_update_builder(input: TokenStream) -> TokenStream {
 pub fn derive_delete_builder(input: TokenStream) -> TokenStream {
     gen::expand(input, gen::Mode::Delete)
 }
+
+/// Declare builders for an existing entity module from outside it, e.g. one
+/// regenerated by sea-orm-cli:
+///
+/// ```ignore
+/// builders!(crate::entities::orders,
+///     fields { id: i64, status: String },
+///     select { id: [eq, in], status: [eq] },
+///     update { id: [eq], status: [set] },
+/// );
+/// ```
+#[proc_macro]
+pub fn builders(input: TokenStream) -> TokenStream {
+    decl::expand(input)
+}
@@ -0,0 +1,170 @@
+//! External permission manifest
+//!
+//! - Optional TOML file read at compile time: `sea_builder.toml` next to the
+//!   crate's Cargo.toml, or the path given in `#[sea_builder(config = "...")]`
+//! - One table per model table and field:
+//!
+//! ```toml
+//! [orders.id]
+//! select = ["eq", "in"]
+//! delete = ["eq"]
+//!
+//! [orders.status]
+//! update = ["eq"]
+//! set = true
+//! ```
+//!
+//! - Ops and presets count as if written on the field itself; `ast::collect` rejects
+//!   fields the model doesn't have
+
+use std::path::PathBuf;
+
+use proc_macro2::Span;
+use syn::LitStr;
+use toml_edit::{DocumentMut, Item};
+
+use crate::ast::{lookup_op, op_spellings, unknown_msg, FieldPerms, OpRef, Preset, WhereOp};
+
+/// Default manifest file name, looked up in `CARGO_MANIFEST_DIR`.
+const DEFAULT_FILE: &str = "sea_builder.toml";
+/// Keys accepted for a field.
+const FIELD_KEYS: &[&str] = &["select", "update", "delete", "set"];
+
+/// The manifest entries of one table.
+#[derive(Debug)]
+pub struct Manifest {
+    /// Absolute path, so codegen can make the build depend on the file.
+    pub path: String,
+    pub fields: Vec<(String, FieldPerms)>,
+    /// Where manifest errors point: the `config` literal or the derive.
+    pub span: Span,
+}
+
+impl Manifest {
+    pub fn perms(&self, field: &str) -> Option<&FieldPerms> {
+        self.fields.iter().find(|(f, _)| f == field).map(|(_, p)| p)
+    }
+
+    pub fn error(&self, msg: impl std::fmt::Display) -> syn::Error {
+        syn::Error::new(self.span, format!("{}: {}", self.path, msg))
+    }
+}
+
+/// Load the entries for `table`. Without `config` a missing default file just
+/// means there is no manifest.
+pub fn load(
+    config: Option<&LitStr>,
+    table: Option<&str>,
+    presets: &[Preset],
+) -> syn::Result<Option<Manifest>> {
+    let span = config.map_or_else(Span::call_site, LitStr::span);
+    let base = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let path = match config {
+        Some(lit) => base.join(lit.value()),
+        None => {
+            let path = base.join(DEFAULT_FILE);
+            if !path.is_file() {
+                return Ok(None);
+            }
+            path
+        }
+    };
+    let mut manifest = Manifest {
+        path: path.display().to_string(),
+        fields: Vec::new(),
+        span,
+    };
+    let text = std::fs::read_to_string(&path)
+        .map_err(|err| manifest.error(format!("cannot read manifest: {}", err)))?;
+    let doc: DocumentMut = text
+        .parse()
+        .map_err(|err| manifest.error(format!("invalid TOML: {}", err)))?;
+
+    let entries = match table.and_then(|t| doc.get(t)) {
+        Some(item) => item,
+        None => return Ok(Some(manifest)),
+    };
+    let table = table.expect("looked up");
+    let entries = entries
+        .as_table_like()
+        .ok_or_else(|| manifest.error(format!("`{}` must be a table of fields", table)))?;
+    for (field, item) in entries.iter() {
+        let perms = parse_field(&manifest, &format!("{}.{}", table, field), item, presets)?;
+        manifest.fields.push((field.to_string(), perms));
+    }
+    Ok(Some(manifest))
+}
+
+fn parse_field(
+    manifest: &Manifest,
+    key: &str,
+    item: &Item,
+    presets: &[Preset],
+) -> syn::Result<FieldPerms> {
+    let entries = item
+        .as_table_like()
+        .ok_or_else(|| manifest.error(format!("`{}` must be a table", key)))?;
+    let mut perms = FieldPerms::default();
+    for (name, value) in entries.iter() {
+        let target = match name {
+            "select" => &mut perms.select_where,
+            "update" => &mut perms.update_where,
+            "delete" => &mut perms.delete_where,
+            "set" => {
+                perms.update_set = value.as_bool().ok_or_else(|| {
+                    manifest.error(format!("`{}.set` must be true or false", key))
+                })?;
+                continue;
+            }
+            other => {
+                return Err(manifest.error(unknown_msg(
+                    other,
+                    &format!("key in `{}`", key),
+                    FIELD_KEYS,
+                )))
+            }
+        };
+        let ops = value
+            .as_array()
+            .ok_or_else(|| manifest.error(format!("`{}.{}` must be an array of ops", key, name)))?;
+        for op in ops.iter() {
+            let spelling = op.as_str().ok_or_else(|| {
+                manifest.error(format!("`{}.{}` must contain op names", key, name))
+            })?;
+            match lookup_op(spelling, presets) {
+                Some(OpRef::Op(op)) => {
+                    if target.iter().any(|w| w.op == op) {
+                        return Err(manifest.error(format!(
+                            "duplicate op `{}` in `{}.{}`",
+                            op.name(),
+                            key,
+                            name
+                        )));
+                    }
+                    target.push(WhereOp {
+                        op,
+                        span: manifest.span,
+                    });
+                }
+                Some(OpRef::Preset(ops)) => {
+                    for op in ops {
+                        if !target.iter().any(|w| w.op == op) {
+                            target.push(WhereOp {
+                                op,
+                                span: manifest.span,
+                            });
+                        }
+                    }
+                }
+                None => {
+                    return Err(manifest.error(unknown_msg(
+                        spelling,
+                        &format!("op in `{}.{}`", key, name),
+                        &op_spellings(presets),
+                    )))
+                }
+            }
+        }
+    }
+    Ok(perms)
+}
@@ -0,0 +1,265 @@
+//! Field type classification
+//!
+//! - Sorts a field's Rust type into a coarse `TypeClass`
+//! - Decides whether a `where(...)` op makes sense for that class
+//! - Types it can't recognise (newtypes, enums, aliases) are checked later
+//!   through marker traits in `sea_orm_builder::kind`
+
+use crate::ast::{backend, Op};
+
+/// Coarse kind of value a column holds.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TypeClass {
+    Textual,
+    Numeric,
+    Temporal,
+    Boolean,
+    Binary,
+    Uuid,
+    Json,
+    Enum,
+    /// `Vec<T>` other than `Vec<u8>`, i.e. a Postgres array.
+    Array,
+    /// Not recognised from the type path; ops are checked with a trait bound.
+    Unknown,
+}
+
+impl TypeClass {
+    /// Spellings accepted by the field-level `kind = "..."` override.
+    pub const KINDS: &'static [(&'static str, TypeClass)] = &[
+        ("text", TypeClass::Textual),
+        ("numeric", TypeClass::Numeric),
+        ("temporal", TypeClass::Temporal),
+        ("boolean", TypeClass::Boolean),
+        ("binary", TypeClass::Binary),
+        ("uuid", TypeClass::Uuid),
+        ("json", TypeClass::Json),
+        ("enum", TypeClass::Enum),
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TypeClass::Textual => "textual",
+            TypeClass::Numeric => "numeric",
+            TypeClass::Temporal => "temporal",
+            TypeClass::Boolean => "boolean",
+            TypeClass::Binary => "binary",
+            TypeClass::Uuid => "uuid",
+            TypeClass::Json => "json",
+            TypeClass::Enum => "enum",
+            TypeClass::Array => "array",
+            TypeClass::Unknown => "unknown",
+        }
+    }
+}
+
+/// Classified field type; `Option<T>` is unwrapped into `optional` + `T`.
+#[derive(Clone, Debug)]
+pub struct FieldType {
+    pub class: TypeClass,
+    pub optional: bool,
+    /// `T` for `Option<T>`, otherwise the field type itself.
+    pub inner: syn::Type,
+}
+
+/// Marker trait an unrecognised type must implement for an op to apply.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Marker {
+    Textual,
+    Ordered,
+}
+
+impl Marker {
+    pub fn trait_name(self) -> &'static str {
+        match self {
+            Marker::Textual => "Textual",
+            Marker::Ordered => "Ordered",
+        }
+    }
+
+    /// "a textual" / "an ordered", for diagnostics.
+    pub fn article_name(self) -> &'static str {
+        match self {
+            Marker::Textual => "a textual",
+            Marker::Ordered => "an ordered",
+        }
+    }
+}
+
+/// Whether an op applies to a class of values.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Support {
+    Yes,
+    No,
+    /// Only if the type implements the marker trait.
+    Bound(Marker),
+}
+
+pub fn support(op: Op, ty: &FieldType) -> Support {
+    if let Some(base) = op.col_base() {
+        return support(base, ty);
+    }
+    let class = ty.class;
+    if class == TypeClass::Array && op.array_shape().is_some() {
+        return if backend() == Some("postgres") {
+            Support::Yes
+        } else {
+            Support::No
+        };
+    }
+    let marker = match op {
+        Op::Eq | Op::Ne | Op::In | Op::NotIn | Op::InSelect | Op::NotInSelect => {
+            return Support::Yes
+        }
+        Op::IsNull | Op::IsNotNull | Op::EqNullSafe if ty.optional => return Support::Yes,
+        Op::IsNull | Op::IsNotNull | Op::EqNullSafe => return Support::No,
+        _ if op.needs_backend() && backend().is_none() => return Support::No,
+        _ if op.bitflag() && is_integer(ty) => return Support::Yes,
+        _ if op.bitflag() => return Support::No,
+        _ if op.temporal() && is_chrono_day(ty) => return Support::Yes,
+        _ if op.temporal() => return Support::No,
+        Op::ContainedBy | Op::Overlaps | Op::AnyEq => return Support::No,
+        Op::Lt | Op::Lte | Op::Gt | Op::Gte | Op::Between | Op::NotBetween => Marker::Ordered,
+        Op::Like
+        | Op::Ilike
+        | Op::NotLike
+        | Op::NotIlike
+        | Op::Ieq
+        | Op::Regex
+        | Op::NotRegex
+        | Op::Fts
+        | Op::StartsWith
+        | Op::EndsWith
+        | Op::Contains
+        | Op::IstartsWith
+        | Op::IendsWith
+        | Op::Icontains => Marker::Textual,
+        Op::HasFlags | Op::HasAnyFlag | Op::LacksFlags => unreachable!("handled above"),
+        Op::On | Op::Before | Op::Since | Op::WithinLast => unreachable!("handled above"),
+        Op::EqCol | Op::NeCol | Op::LtCol | Op::LteCol | Op::GtCol | Op::GteCol => {
+            unreachable!("handled above")
+        }
+    };
+    let ok = match (marker, class) {
+        (_, TypeClass::Unknown) => return Support::Bound(marker),
+        (Marker::Ordered, c) => matches!(
+            c,
+            TypeClass::Textual | TypeClass::Numeric | TypeClass::Temporal | TypeClass::Uuid
+        ),
+        (Marker::Textual, c) => c == TypeClass::Textual,
+    };
+    if ok {
+        Support::Yes
+    } else {
+        Support::No
+    }
+}
+
+/// Whether the field holds a primitive integer, the only type bitflag ops
+/// apply to.
+pub fn is_integer(ty: &FieldType) -> bool {
+    ty.class == TypeClass::Numeric
+        && matches!(
+            last_ident(&ty.inner).as_deref(),
+            Some("i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64")
+        )
+}
+
+/// Whether the field is chrono's `DateTimeUtc` (`DateTime<Utc>`) or `Date`
+/// (`NaiveDate`), which `sea_orm_builder::gen::Temporal` is implemented for.
+pub fn is_chrono_day(ty: &FieldType) -> bool {
+    if let Some(tz) = generic_arg(&ty.inner, "DateTime") {
+        return last_ident(tz).as_deref() == Some("Utc");
+    }
+    matches!(
+        last_ident(&ty.inner).as_deref(),
+        Some("DateTimeUtc" | "ChronoDateTimeUtc" | "Date" | "ChronoDate" | "NaiveDate")
+    )
+}
+
+pub fn classify(ty: &syn::Type) -> FieldType {
+    if let Some(inner) = generic_arg(ty, "Option") {
+        return FieldType {
+            class: classify_plain(inner),
+            optional: true,
+            inner: inner.clone(),
+        };
+    }
+    FieldType {
+        class: classify_plain(ty),
+        optional: false,
+        inner: ty.clone(),
+    }
+}
+
+fn classify_plain(ty: &syn::Type) -> TypeClass {
+    if let Some(inner) = generic_arg(ty, "Vec") {
+        return match last_ident(inner).as_deref() {
+            Some("u8") => TypeClass::Binary,
+            _ => TypeClass::Array,
+        };
+    }
+    let path = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return TypeClass::Unknown,
+    };
+    let last = match path.segments.last() {
+        Some(seg) => seg.ident.to_string(),
+        None => return TypeClass::Unknown,
+    };
+    match last.as_str() {
+        "String" => TypeClass::Textual,
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "f32" | "f64" | "Decimal"
+        | "BigDecimal" => TypeClass::Numeric,
+        "Date"
+        | "Time"
+        | "DateTime"
+        | "DateTimeUtc"
+        | "DateTimeLocal"
+        | "DateTimeWithTimeZone"
+        | "NaiveDate"
+        | "NaiveTime"
+        | "NaiveDateTime"
+        | "TimeDate"
+        | "TimeTime"
+        | "TimeDateTime"
+        | "TimeDateTimeWithTimeZone"
+        | "OffsetDateTime"
+        | "PrimitiveDateTime" => TypeClass::Temporal,
+        "bool" => TypeClass::Boolean,
+        "Uuid" => TypeClass::Uuid,
+        "Json" => TypeClass::Json,
+        "Value" if path.segments.len() > 1 && path.segments[0].ident == "serde_json" => {
+            TypeClass::Json
+        }
+        _ => TypeClass::Unknown,
+    }
+}
+
+/// `T` when `ty` is `<wrapper><T>` (matched on the last path segment).
+pub fn generic_arg<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    let path = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return None,
+    };
+    let seg = path.segments.last()?;
+    if seg.ident != wrapper {
+        return None;
+    }
+    match &seg.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first()? {
+                syn::GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn last_ident(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
@@ -1,8 +1,13 @@
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::sea_query::SelectStatement;
+use sea_orm::{EntityTrait, QueryTrait, Select};
+
 /// Generic converter so String fields accept both &str and String; identity for others.
+/// Nullable fields also accept a bare `T` (and `&str` for `Option<String>`).
 pub trait IntoField<T> {
     fn into_field(self) -> T;
 }
-impl<'a> IntoField<String> for &'a str {
+impl IntoField<String> for &str {
     #[inline]
     fn into_field(self) -> String {
         self.to_owned()
@@ -14,15 +19,447 @@ impl<T> IntoField<T> for T {
         self
     }
 }
+impl<T> IntoField<Option<T>> for T {
+    #[inline]
+    fn into_field(self) -> Option<T> {
+        Some(self)
+    }
+}
+impl IntoField<Option<String>> for &str {
+    #[inline]
+    fn into_field(self) -> Option<String> {
+        Some(self.to_owned())
+    }
+}
+
+/// A query usable as `IN (SELECT ..)` for a field of type `T`, with the
+/// where params it was built from.
+///
+/// Generated Select builders implement it for their primary key type and
+/// select only the key. A plain `Select<E>` is taken as is, for any `T`, so
+/// project the one matching column yourself:
+/// `customer::Entity::find().select_only().column(customer::Column::Id)`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be a subquery for a `{T}` field",
+    note = "a generated Select builder selects its primary key, which must have type `{T}`"
+)]
+pub trait IntoSubquery<T> {
+    fn into_subquery(self) -> (SelectStatement, Vec<WhereParam>);
+}
+
+impl<E: EntityTrait, T> IntoSubquery<T> for Select<E> {
+    fn into_subquery(self) -> (SelectStatement, Vec<WhereParam>) {
+        (QueryTrait::into_query(self), Vec::new())
+    }
+}
 
-/// Re-exports used by generated code
+/// Re-exports and helpers used by generated code
 pub mod gen {
     pub use crate::IntoField;
     pub use sea_orm::{
-        sea_query::{Expr, ValueType},
+        sea_query::{extension::postgres::PgExpr, Expr, ExprTrait, ValueType},
         ColumnTrait, DeleteMany, EntityTrait, Order, QueryFilter, QueryOrder, QuerySelect, Select,
         UpdateMany,
     };
+
+    pub use sea_orm::prelude::Date;
+    pub use sea_orm::sea_query::SelectStatement;
+    pub use sea_orm::PrimaryKeyTrait;
+
+    use sea_orm::prelude::{DateTimeUtc, Time};
+    use sea_orm::sea_query::{BinOper, Func, IntoValueTuple, SimpleExpr};
+    use sea_orm::Value;
+    use sea_orm::{Iterable, PrimaryKeyToColumn, QueryTrait};
+
+    #[derive(Copy, Clone, PartialEq, Eq)]
+    enum Backend {
+        Postgres,
+        MySql,
+        Sqlite,
+    }
+
+    /// The backend feature in effect, first of postgres, mysql, sqlite.
+    /// Custom expressions for it use its placeholders: numbered `$1` on
+    /// Postgres, positional `?` on MySQL and SQLite.
+    const BACKEND: Option<Backend> = if cfg!(feature = "postgres") {
+        Some(Backend::Postgres)
+    } else if cfg!(feature = "mysql") {
+        Some(Backend::MySql)
+    } else if cfg!(feature = "sqlite") {
+        Some(Backend::Sqlite)
+    } else {
+        None
+    };
+
+    /// `col` equals `v`, with `None` matching NULL. Rendered with the native
+    /// operator of the enabled backend feature; without one it becomes
+    /// `col IS NULL` or `col = v`, which is equivalent in a WHERE clause.
+    pub fn eq_null_safe<C: ColumnTrait, T>(col: C, v: Option<T>) -> SimpleExpr
+    where
+        T: Into<Value>,
+        Option<T>: Into<Value>,
+    {
+        let template = match BACKEND {
+            Some(Backend::Postgres) => "$1 IS NOT DISTINCT FROM $2",
+            Some(Backend::MySql) => "? <=> ?",
+            Some(Backend::Sqlite) => "? IS ?",
+            None => {
+                return match v {
+                    None => col.is_null(),
+                    Some(v) => col.eq(v),
+                }
+            }
+        };
+        Expr::cust_with_exprs(template, [col.into_expr().into(), Expr::val(v).into()])
+    }
+
+    /// `col ~ pattern` on Postgres, `col REGEXP pattern` on MySQL and SQLite.
+    /// Without a backend the derives reject `regex`/`not_regex`, so the
+    /// `REGEXP` fallback is never reached by generated code.
+    pub fn regex_match<C: ColumnTrait>(col: C, pattern: String, negate: bool) -> SimpleExpr {
+        let template = match (BACKEND, negate) {
+            (Some(Backend::Postgres), false) => "$1 ~ $2",
+            (Some(Backend::Postgres), true) => "$1 !~ $2",
+            (_, false) => "? REGEXP ?",
+            (_, true) => "? NOT REGEXP ?",
+        };
+        Expr::cust_with_exprs(
+            template,
+            [col.into_expr().into(), Expr::val(pattern).into()],
+        )
+    }
+
+    /// How `json_field` extracts a value, from the key's declared type.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum JsonAs {
+        Text,
+        Number,
+        Bool,
+    }
+
+    /// The value at `key` of a JSON column: `col ->> 'key'` (cast for
+    /// numbers and booleans) on Postgres, `JSON_EXTRACT(col, '$.key')` on
+    /// MySQL (unquoted for text) and `json_extract(col, '$.key')` on SQLite,
+    /// whose spelling is also the fallback; `json_path` needs a backend.
+    pub fn json_field<C: ColumnTrait>(col: C, key: &str, extract_as: JsonAs) -> SimpleExpr {
+        let (template, key) = match (BACKEND, extract_as) {
+            (Some(Backend::Postgres), JsonAs::Text) => ("$1 ->> $2", key.to_owned()),
+            (Some(Backend::Postgres), JsonAs::Number) => {
+                ("CAST($1 ->> $2 AS numeric)", key.to_owned())
+            }
+            (Some(Backend::Postgres), JsonAs::Bool) => {
+                ("CAST($1 ->> $2 AS boolean)", key.to_owned())
+            }
+            (Some(Backend::MySql), JsonAs::Text) => {
+                ("JSON_UNQUOTE(JSON_EXTRACT(?, ?))", format!("$.{}", key))
+            }
+            (Some(Backend::MySql), _) => ("JSON_EXTRACT(?, ?)", format!("$.{}", key)),
+            (_, _) => ("json_extract(?, ?)", format!("$.{}", key)),
+        };
+        Expr::cust_with_exprs(template, [col.into_expr().into(), Expr::val(key).into()])
+    }
+
+    /// Full-text match of `query` against `cols`:
+    /// `to_tsvector(..) @@ plainto_tsquery(query)` on Postgres (several
+    /// columns joined with `concat_ws`), `MATCH (..) AGAINST (query)` on MySQL
+    /// (which needs a FULLTEXT index over the same columns) and a
+    /// case-insensitive `LIKE '%query%'` on any of them on SQLite. Both `fts`
+    /// and the struct-level `search` need a backend feature to be generated.
+    pub fn full_text<C: ColumnTrait>(
+        cols: impl IntoIterator<Item = C>,
+        query: String,
+    ) -> SimpleExpr {
+        let cols: Vec<C> = cols.into_iter().collect();
+        let template = match BACKEND {
+            Some(Backend::Postgres) => {
+                let numbered: Vec<String> = (1..=cols.len()).map(|i| format!("${}", i)).collect();
+                let document = match cols.len() {
+                    1 => numbered[0].clone(),
+                    _ => format!("concat_ws(' ', {})", numbered.join(", ")),
+                };
+                format!(
+                    "to_tsvector({}) @@ plainto_tsquery(${})",
+                    document,
+                    cols.len() + 1
+                )
+            }
+            Some(Backend::MySql) => format!(
+                "MATCH ({}) AGAINST (? IN NATURAL LANGUAGE MODE)",
+                vec!["?"; cols.len()].join(", ")
+            ),
+            _ => {
+                let pattern = like_pattern(&query, true, true);
+                return cols
+                    .into_iter()
+                    .map(|col| like_escaped(col, pattern.clone(), true))
+                    .reduce(ExprTrait::or)
+                    .expect("at least one column");
+            }
+        };
+        let exprs = cols
+            .into_iter()
+            .map(|col| col.into_expr().into())
+            .chain([Expr::val(query).into()]);
+        Expr::cust_with_exprs(template, exprs)
+    }
+
+    /// Postgres array comparisons.
+    #[cfg(feature = "postgres")]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum ArrayOp {
+        /// `@>`
+        Contains,
+        /// `<@`
+        ContainedBy,
+        /// `&&`
+        Overlaps,
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn array_op<C: ColumnTrait, T>(col: C, op: ArrayOp, v: Vec<T>) -> SimpleExpr
+    where
+        Vec<T>: Into<Value>,
+    {
+        use sea_orm::sea_query::extension::postgres::PgBinOper;
+        let op = match op {
+            ArrayOp::Contains => PgBinOper::Contains,
+            ArrayOp::ContainedBy => PgBinOper::Contained,
+            ArrayOp::Overlaps => PgBinOper::Overlap,
+        };
+        Expr::expr(col.into_expr()).binary(op, Expr::val(v))
+    }
+
+    /// `v = ANY(col)`
+    #[cfg(feature = "postgres")]
+    pub fn array_any_eq<C: ColumnTrait, T: Into<Value>>(col: C, v: T) -> SimpleExpr {
+        Expr::cust_with_exprs(
+            "$1 = ANY($2)",
+            [Expr::val(v).into(), col.into_expr().into()],
+        )
+    }
+
+    /// Bitmask tests on integer columns.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum FlagOp {
+        /// `(col & mask) = mask`
+        All,
+        /// `(col & mask) <> 0`
+        Any,
+        /// `(col & mask) = 0`
+        None,
+    }
+
+    /// `col & mask` compared as `op` says; plain SQL on every backend.
+    pub fn flags<C: ColumnTrait, T: Into<Value> + Clone>(
+        col: C,
+        op: FlagOp,
+        mask: T,
+    ) -> SimpleExpr {
+        let masked = col.into_expr().bit_and(mask.clone());
+        match op {
+            FlagOp::All => masked.eq(mask),
+            FlagOp::Any => masked.ne(0),
+            FlagOp::None => masked.eq(0),
+        }
+    }
+
+    /// Field types the temporal ops (`on`, `before`, `since`, `within_last`)
+    /// apply to: chrono's `DateTimeUtc` and `Date`.
+    pub trait Temporal: Sized {
+        /// The first instant of `date` (UTC) and of the day after, so `on`
+        /// can match `start <= col < end` without losing the last second.
+        /// Past chrono's last day the end clamps to the latest value.
+        fn day(date: Date) -> (Self, Self);
+        /// The instant `at` as a value of this type.
+        fn at(at: DateTimeUtc) -> Self;
+    }
+
+    impl Temporal for DateTimeUtc {
+        fn day(date: Date) -> (Self, Self) {
+            let end = match date.succ_opt() {
+                Some(next) => next.and_time(Time::MIN).and_utc(),
+                None => DateTimeUtc::MAX_UTC,
+            };
+            (date.and_time(Time::MIN).and_utc(), end)
+        }
+
+        fn at(at: DateTimeUtc) -> Self {
+            at
+        }
+    }
+
+    impl Temporal for Date {
+        fn day(date: Date) -> (Self, Self) {
+            (date, date.succ_opt().unwrap_or(Date::MAX))
+        }
+
+        fn at(at: DateTimeUtc) -> Self {
+            at.date_naive()
+        }
+    }
+
+    /// `(a, b, ..) IN ((..), ..)` for `tuple_in`. SQLite only takes a
+    /// subquery on the right of a row-value `IN`, so with the `sqlite` feature
+    /// it is spelled `(a = .. AND b = ..) OR ..` instead.
+    pub fn tuple_in<C: ColumnTrait + Copy, V: IntoValueTuple>(
+        cols: &[C],
+        values: Vec<V>,
+    ) -> SimpleExpr {
+        if BACKEND == Some(Backend::Sqlite) && !values.is_empty() {
+            return values
+                .into_iter()
+                .map(|v| {
+                    cols.iter()
+                        .zip(v.into_value_tuple())
+                        .map(|(col, v)| col.into_expr().eq(v))
+                        .reduce(ExprTrait::and)
+                        .expect("at least two columns")
+                })
+                .reduce(ExprTrait::or)
+                .expect("at least one tuple");
+        }
+        Expr::tuple(cols.iter().map(|col| col.into_expr().into())).in_tuples(values)
+    }
+
+    /// `select` narrowed to its entity's primary key, for `IN (SELECT ..)`.
+    /// A composite key selects all of its columns, matching its tuple type.
+    pub fn pk_subquery<E: EntityTrait>(select: Select<E>) -> SelectStatement {
+        let keys = <E::PrimaryKey as Iterable>::iter().map(PrimaryKeyToColumn::into_column);
+        QueryTrait::into_query(QuerySelect::columns(QuerySelect::select_only(select), keys))
+    }
+
+    /// `(now - d, now)` by `clock`, as the field type. A `d` reaching past
+    /// chrono's first instant starts there instead.
+    pub fn last<T: Temporal>(clock: &dyn crate::Clock, d: ::std::time::Duration) -> (T, T) {
+        let now = clock.now();
+        let start = match (now - DateTimeUtc::MIN_UTC).to_std() {
+            Ok(span) if d <= span => now - d,
+            _ => DateTimeUtc::MIN_UTC,
+        };
+        (T::at(start), T::at(now))
+    }
+
+    /// `LOWER(col) = LOWER(v)`, the same SQL on every backend.
+    pub fn eq_ignore_case<C: ColumnTrait>(col: C, v: String) -> SimpleExpr {
+        Expr::expr(Func::lower(col.into_expr())).eq(Func::lower(Expr::val(v)))
+    }
+
+    /// `s` escaped with [`escape_like`](crate::escape_like), with `%` added
+    /// where the text may continue.
+    pub fn like_pattern(s: &str, leading: bool, trailing: bool) -> String {
+        let wildcard = |on: bool| if on { "%" } else { "" };
+        format!(
+            "{}{}{}",
+            wildcard(leading),
+            crate::escape_like(s),
+            wildcard(trailing)
+        )
+    }
+
+    /// `col LIKE pattern ESCAPE '\'`, or with both sides wrapped in `LOWER`
+    /// when `ignore_case` is set, which works the same on every backend.
+    pub fn like_escaped<C: ColumnTrait>(col: C, pattern: String, ignore_case: bool) -> SimpleExpr {
+        let (left, right): (SimpleExpr, SimpleExpr) = if ignore_case {
+            (
+                Func::lower(col.into_expr()).into(),
+                Func::lower(Expr::val(pattern)).into(),
+            )
+        } else {
+            (col.into_expr().into(), Expr::val(pattern).into())
+        };
+        let right = SimpleExpr::Binary(
+            Box::new(right),
+            BinOper::Escape,
+            Box::new(SimpleExpr::Constant(Value::from(crate::LIKE_ESCAPE))),
+        );
+        Expr::expr(left).binary(BinOper::Like, right)
+    }
+}
+
+/// Source of "now" for `within_last`. Builders use [`SystemClock`] unless
+/// given another one with `with_clock`, e.g. a [`FixedClock`] in tests.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTimeUtc;
+}
+
+/// The system time.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTimeUtc {
+        DateTimeUtc::from(std::time::SystemTime::now())
+    }
+}
+
+/// Always the given instant.
+#[derive(Copy, Clone, Debug)]
+pub struct FixedClock(pub DateTimeUtc);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTimeUtc {
+        self.0
+    }
+}
+
+/// Escape character used by the `starts_with`/`ends_with`/`contains` ops.
+const LIKE_ESCAPE: char = '\\';
+
+/// Escapes `%`, `_` and `\` so `s` matches itself in a LIKE pattern with
+/// `ESCAPE '\'`.
+pub fn escape_like(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | LIKE_ESCAPE) {
+            out.push(LIKE_ESCAPE);
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Marker traits the derives use to check ops on field types they can't
+/// classify from the type path (newtypes, active enums, aliases).
+///
+/// Implement them for your own column types to opt in, e.g.
+/// `impl sea_orm_builder::kind::Textual for Email {}` allows `like` on an
+/// `Email` field.
+pub mod kind {
+    use sea_orm::prelude::{
+        BigDecimal, Date, DateTime, DateTimeLocal, DateTimeUtc, DateTimeWithTimeZone, Decimal,
+        Time, TimeDate, TimeDateTime, TimeDateTimeWithTimeZone, TimeTime, Uuid,
+    };
+
+    /// Values matched with `like`/`ilike`; the pattern is built from the
+    /// value's `String` form.
+    pub trait Textual: Into<String> {}
+
+    /// Values compared with `lt`/`lte`/`gt`/`gte`/`between`.
+    pub trait Ordered {}
+
+    impl Textual for String {}
+
+    macro_rules! ordered {
+        ($($t:ty),* $(,)?) => { $(impl Ordered for $t {})* };
+    }
+    ordered!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, Decimal, BigDecimal);
+    ordered!(
+        String,
+        Uuid,
+        Date,
+        Time,
+        DateTime,
+        DateTimeUtc,
+        DateTimeLocal
+    );
+    ordered!(
+        DateTimeWithTimeZone,
+        TimeDate,
+        TimeTime,
+        TimeDateTime,
+        TimeDateTimeWithTimeZone
+    );
+    impl<T: Ordered> Ordered for Option<T> {}
 }
 
 // Simple error type used by generated builders
@@ -35,7 +472,7 @@ pub enum SeaOrmBuilderError {
 }
 
 // Re-export the derive macros so users only depend on sea_orm_builder
-pub use sea_orm_builder_derive::{DeleteBuilder, SelectBuilder, UpdateBuilder};
+pub use sea_orm_builder_derive::{builders, DeleteBuilder, SelectBuilder, UpdateBuilder};
 
 // Metadata captured for where clauses
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -49,5 +486,14 @@ pub struct WhereParam {
 pub enum WhereValue {
     Single(String),
     List(Vec<String>),
-    Range { start: String, end: String },
+    Range {
+        start: String,
+        end: String,
+    },
+    /// Ops without an input, such as `is_null`.
+    None,
+    /// The where params of a subquery, for `in_select`/`not_in_select`.
+    Nested(Vec<WhereParam>),
+    /// Another column of the entity, by field name, for the `<op>_col` methods.
+    Column(String),
 }
@@ -0,0 +1,66 @@
+//! Needs the postgres feature: `cargo test --features postgres`.
+#![cfg(feature = "postgres")]
+
+mod common;
+
+mod post {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "post")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(eq, contains, contained_by, overlaps, any_eq)))]
+        pub tags: Vec<String>,
+
+        #[sea_builder(select(where(contains)))]
+        pub scores: Option<Vec<i32>>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::{DbBackend, QueryTrait};
+use sea_orm_builder::WhereValue;
+
+#[test]
+fn array_ops_take_elements() {
+    let (stmt, params) = post::PostSelect::new()
+        .tags_contains(["rust", "orm"])
+        .tags_contained_by(vec!["a".to_string()])
+        .tags_overlaps(["x"])
+        .tags_any_eq("news")
+        .scores_contains([10])
+        .build_with_params();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Postgres),
+        r#""post"."tags" @> ARRAY ['rust','orm'] AND "post"."tags" <@ ARRAY ['a'] AND ("post"."tags" && ARRAY ['x']) AND ('news' = ANY("post"."tags")) AND "post"."scores" @> ARRAY [10]"#
+    );
+
+    let tags: Option<&[String]> = params.get_tags_contains();
+    assert_eq!(tags, Some(&["rust".to_string(), "orm".to_string()][..]));
+    assert_eq!(params.get_tags_any_eq(), Some(&"news".to_string()));
+    assert_eq!(params.get_scores_contains(), Some(&[10][..]));
+    assert_eq!(
+        params.where_params()[2].value,
+        WhereValue::List(vec![r#""x""#.into()])
+    );
+}
+
+#[test]
+fn eq_still_compares_the_whole_array() {
+    let stmt = post::PostSelect::new()
+        .tags_eq(vec!["a".to_string()])
+        .build();
+    let sql = stmt.build(DbBackend::Postgres).to_string();
+    assert!(
+        sql.ends_with(r#"WHERE "post"."tags" = ARRAY ['a']"#),
+        "{sql}"
+    );
+}
@@ -0,0 +1,82 @@
+mod common;
+
+mod account {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder)]
+    #[sea_orm(table_name = "account")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(update(where(eq)))]
+        pub id: i64,
+
+        #[sea_builder(select(where(has_flags, has_any_flag, lacks_flags)), update(set))]
+        pub perms: i64,
+
+        #[sea_builder(update(set))]
+        pub logins: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::{DbBackend, QueryTrait};
+use sea_orm_builder::{IntoField, WhereValue};
+
+/// Stand-in for a `bitflags!` type, which converts through `IntoField`.
+#[derive(Copy, Clone)]
+struct Perms(i64);
+
+impl Perms {
+    const READ: Perms = Perms(1);
+    const WRITE: Perms = Perms(2);
+    const ADMIN: Perms = Perms(8);
+}
+
+impl IntoField<i64> for Perms {
+    fn into_field(self) -> i64 {
+        self.0
+    }
+}
+
+#[test]
+fn flag_tests_mask_the_column() {
+    let (stmt, params) = account::AccountSelect::new()
+        .perms_has_flags(3)
+        .perms_has_any_flag(Perms::WRITE)
+        .perms_lacks_flags(Perms::ADMIN)
+        .build_with_params();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Sqlite),
+        r#"("account"."perms" & 3) = 3 AND ("account"."perms" & 2) <> 0 AND ("account"."perms" & 8) = 0"#
+    );
+    assert_eq!(params.get_perms_has_any_flag(), Some(&2));
+    assert_eq!(
+        params.where_params()[2].value,
+        WhereValue::Single("Some(8)".into())
+    );
+}
+
+#[test]
+fn set_adds_and_removes_flags() {
+    let sql = |update: account::AccountUpdate| {
+        update
+            .id_eq(1)
+            .build()
+            .unwrap()
+            .build(DbBackend::Sqlite)
+            .to_string()
+    };
+    assert_eq!(
+        sql(account::AccountUpdate::new().set_perms_add_flags(Perms::READ)),
+        r#"UPDATE "account" SET "perms" = "perms" | 1 WHERE "account"."id" = 1"#
+    );
+    assert_eq!(
+        sql(account::AccountUpdate::new().set_perms_remove_flags(Perms::ADMIN)),
+        r#"UPDATE "account" SET "perms" = "perms" & -9 WHERE "account"."id" = 1"#
+    );
+}
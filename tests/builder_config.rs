@@ -0,0 +1,59 @@
+// Two entities on the same table name (e.g. different schemas) would both
+// produce `OrdersSelect`; `name` tells them apart.
+mod orders {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder)]
+    #[sea_orm(table_name = "orders")]
+    #[sea_builder(vis = "pub(crate)", module(name = "builders", select, update))]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(eq)), update(where(eq)))]
+        pub id: i64,
+
+        #[sea_builder(update(set))]
+        pub status: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod archived_orders {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(schema_name = "archive", table_name = "orders")]
+    #[sea_builder(name = "ArchivedOrders", vis = "pub(crate)")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(eq, in)))]
+        pub id: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use orders::builders::{OrdersSelect, OrdersSelectParams, OrdersUpdate};
+
+#[test]
+fn module_gathers_every_builder() {
+    let (_stmt, params): (_, OrdersSelectParams) = OrdersSelect::new().id_eq(7).build_with_params();
+    assert_eq!(params.get_id_eq(), Some(&7));
+
+    let update = OrdersUpdate::new().set_status("shipped").id_eq(7).build();
+    assert!(update.is_ok());
+}
+
+#[test]
+fn name_overrides_table_prefix() {
+    let (_stmt, params) = archived_orders::ArchivedOrdersSelect::new()
+        .id_in([1, 2])
+        .build_with_params();
+    assert_eq!(params.get_id_in(), Some(&[1, 2][..]));
+}
@@ -0,0 +1,107 @@
+// Stands in for a sea-orm-cli generated file: no builder attributes.
+mod orders {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "orders")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+        pub status: String,
+        pub r#type: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+sea_orm_builder::builders!(
+    orders,
+    fields { id: i64, status: String, r#type: String },
+    select { id: [eq, in], status: [eq, like] },
+    update { id: [eq], status: [set], r#type: [set] },
+    delete { id: [eq] },
+);
+
+mod archive {
+    sea_orm_builder::builders!(
+        super::orders,
+        name = "ArchivedOrders",
+        vis = "pub(crate)",
+        fields { id: i64 },
+        select { id: [isin] },
+    );
+}
+
+// A column renamed with `enum_name` is declared with its variant.
+mod tickets {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "tickets")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false, enum_name = "TicketId")]
+        pub id: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+sea_orm_builder::builders!(
+    tickets,
+    fields {
+        id: i64 as TicketId
+    },
+    select { id: [eq] },
+);
+
+use sea_orm::{DbBackend, QueryTrait};
+
+#[test]
+fn builders_macro_generates_from_outside_the_entity() {
+    let (stmt, params) = OrdersSelect::new()
+        .id_in([1, 2])
+        .status_like("open%")
+        .build_with_params();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "orders"."id", "orders"."status", "orders"."type" FROM "orders" WHERE "orders"."id" IN (1, 2) AND "orders"."status" LIKE 'open%'"#
+    );
+    assert_eq!(params.get_id_in(), Some(&[1, 2][..]));
+
+    let update = OrdersUpdate::new()
+        .set_status("closed")
+        .set_type("manual")
+        .id_eq(3)
+        .build()
+        .unwrap();
+    assert_eq!(
+        update.build(DbBackend::Postgres).to_string(),
+        r#"UPDATE "orders" SET "status" = 'closed', "type" = 'manual' WHERE "orders"."id" = 3"#
+    );
+
+    assert_eq!(
+        OrdersDelete::new().build().err(),
+        Some(sea_orm_builder::SeaOrmBuilderError::NoWhere)
+    );
+}
+
+#[test]
+fn builders_macro_accepts_name_and_vis() {
+    let (_stmt, params) = archive::ArchivedOrdersSelect::new()
+        .id_in([7])
+        .build_with_params();
+    assert!(params.is_id_in());
+}
+
+#[test]
+fn builders_macro_takes_the_column_variant() {
+    let stmt = TicketsSelect::new().id_eq(4).build();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "tickets"."ticket_id" FROM "tickets" WHERE "tickets"."ticket_id" = 4"#
+    );
+}
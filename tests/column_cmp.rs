@@ -0,0 +1,79 @@
+mod common;
+
+mod account {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder)]
+    #[sea_orm(table_name = "account")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(cmp_col(lt, le))), update(where(cmp_col(gt))))]
+        pub balance: Decimal,
+
+        #[sea_builder(update(set))]
+        pub credit_limit: Decimal,
+
+        #[sea_builder(select(where(gt_col)))]
+        pub updated_at: DateTimeUtc,
+
+        pub created_at: DateTimeUtc,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::prelude::Decimal;
+use sea_orm::{DbBackend, QueryTrait};
+use sea_orm_builder::{WhereParam, WhereValue};
+
+use account::{
+    AccountSelectBalanceCol, AccountSelectUpdatedAtCol, AccountUpdateBalanceCol, Column,
+};
+
+#[test]
+fn compares_with_another_column() {
+    let (stmt, params) = account::AccountSelect::new()
+        .balance_lt_col(AccountSelectBalanceCol::CreditLimit)
+        .updated_at_gt_col(AccountSelectUpdatedAtCol::CreatedAt)
+        .build_with_params();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Sqlite),
+        r#""account"."balance" < "account"."credit_limit" AND "account"."updated_at" > "account"."created_at""#
+    );
+    assert!(matches!(
+        params.get_balance_lt_col(),
+        Some(Column::CreditLimit)
+    ));
+    assert!(!params.is_balance_lte_col());
+    assert!(matches!(
+        AccountSelectUpdatedAtCol::CreatedAt.into(),
+        Column::CreatedAt
+    ));
+    assert_eq!(
+        params.where_params()[1],
+        WhereParam {
+            field: "updated_at",
+            op: "gt_col",
+            value: WhereValue::Column("created_at".into())
+        }
+    );
+}
+
+#[test]
+fn update_where_compares_columns() {
+    let stmt = account::AccountUpdate::new()
+        .set_credit_limit(Decimal::new(500, 0))
+        .balance_gt_col(AccountUpdateBalanceCol::CreditLimit)
+        .build()
+        .unwrap();
+    assert_eq!(
+        stmt.build(DbBackend::Sqlite).to_string(),
+        r#"UPDATE "account" SET "credit_limit" = 500 WHERE "account"."balance" > "account"."credit_limit""#
+    );
+}
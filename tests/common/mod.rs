@@ -0,0 +1,11 @@
+//! Helpers shared by the integration tests, pulled in with `mod common;`.
+#![allow(dead_code)] // each test crate uses its own subset
+
+use sea_orm::{DbBackend, QueryTrait};
+
+/// Everything after the first ` WHERE ` of `stmt` rendered for `backend`.
+pub fn where_sql(stmt: &impl QueryTrait, backend: DbBackend) -> String {
+    let sql = stmt.build(backend).to_string();
+    let (_, conditions) = sql.split_once(" WHERE ").expect("a WHERE clause");
+    conditions.to_owned()
+}
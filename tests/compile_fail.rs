@@ -0,0 +1,16 @@
+// Attribute mistakes must be rejected by the derives at compile time; each
+// case in tests/ui pins the diagnostic in its .stderr file.
+
+#[test]
+fn attribute_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    // ops without portable SQL are only rejected without a backend feature
+    if cfg!(not(any(
+        feature = "postgres",
+        feature = "mysql",
+        feature = "sqlite"
+    ))) {
+        t.compile_fail("tests/ui/no_backend/*.rs");
+    }
+}
@@ -0,0 +1,97 @@
+pub mod db {
+    pub mod orders {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "orders")]
+        pub struct Model {
+            #[sea_orm(primary_key, auto_increment = false)]
+            pub id: i64,
+            pub status: String,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    pub mod invoices {
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "invoices")]
+        pub struct Model {
+            #[sea_orm(primary_key, auto_increment = false)]
+            pub id: i64,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+}
+
+// A filter struct living away from its entity, next to an unrelated `Column`
+// and without any SeaORM traits in scope.
+mod filters {
+    #[allow(unused_imports)]
+    use crate::db::invoices::Column;
+    use sea_orm_builder::{SelectBuilder, UpdateBuilder};
+
+    #[allow(dead_code)]
+    #[derive(SelectBuilder, UpdateBuilder)]
+    #[sea_builder(name = "Orders", entity = "crate::db::orders::Entity")]
+    pub struct OrderFilter {
+        #[sea_builder(select(where(eq, in)), update(where(eq)))]
+        pub id: i64,
+        #[sea_builder(select(where(like)), update(set))]
+        pub status: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(SelectBuilder)]
+    #[sea_builder(
+        name = "Invoices",
+        module(name = "builders", select),
+        entity = "super::db::invoices::Entity",
+        column = "super::db::invoices::Column"
+    )]
+    pub struct InvoiceFilter {
+        #[sea_builder(select(where(eq)))]
+        pub id: i64,
+    }
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+
+#[test]
+fn entity_override_resolves_entity_and_column() {
+    let stmt = filters::OrdersSelect::new()
+        .id_in([1, 2])
+        .status_like("new%")
+        .order_by_asc(db::orders::Column::Id)
+        .build();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "orders"."id", "orders"."status" FROM "orders" WHERE "orders"."id" IN (1, 2) AND "orders"."status" LIKE 'new%' ORDER BY "orders"."id" ASC"#
+    );
+
+    let update = filters::OrdersUpdate::new()
+        .set_status("paid")
+        .id_eq(1)
+        .build()
+        .unwrap();
+    assert_eq!(
+        update.build(DbBackend::Postgres).to_string(),
+        r#"UPDATE "orders" SET "status" = 'paid' WHERE "orders"."id" = 1"#
+    );
+}
+
+#[test]
+fn relative_paths_follow_generated_module() {
+    let stmt = filters::builders::InvoicesSelect::new().id_eq(5).build();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "invoices"."id" FROM "invoices" WHERE "invoices"."id" = 5"#
+    );
+}
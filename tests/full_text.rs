@@ -0,0 +1,101 @@
+//! Needs a backend feature: `cargo test --features postgres` (or `mysql`, `sqlite`).
+#![cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+
+mod common;
+
+mod note {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "note")]
+    #[sea_builder(search(fields = [title, body]))]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(fts)))]
+        pub title: String,
+
+        pub body: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use common::where_sql;
+use sea_orm::DbBackend;
+use sea_orm_builder::{WhereParam, WhereValue};
+
+#[test]
+fn params_keep_the_raw_query() {
+    let (_, params) = note::NoteSelect::new()
+        .search("rust orm")
+        .title_fts("sea")
+        .build_with_params();
+    assert!(params.is_search());
+    assert_eq!(params.get_search(), Some(&"rust orm".to_string()));
+    assert_eq!(params.get_title_fts(), Some(&"sea".to_string()));
+    assert_eq!(
+        params.where_params()[0],
+        WhereParam {
+            field: "title,body",
+            op: "search",
+            value: WhereValue::Single(r#"Some("rust orm")"#.into())
+        }
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn renders_tsvector_on_postgres() {
+    assert_eq!(
+        where_sql(
+            &note::NoteSelect::new().search("rust orm").build(),
+            DbBackend::Postgres
+        ),
+        r#"to_tsvector(concat_ws(' ', "note"."title", "note"."body")) @@ plainto_tsquery('rust orm')"#
+    );
+    assert_eq!(
+        where_sql(
+            &note::NoteSelect::new().title_fts("sea").build(),
+            DbBackend::Postgres
+        ),
+        r#"to_tsvector("note"."title") @@ plainto_tsquery('sea')"#
+    );
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+#[test]
+fn renders_match_against_on_mysql() {
+    assert_eq!(
+        where_sql(
+            &note::NoteSelect::new().search("rust orm").build(),
+            DbBackend::MySql
+        ),
+        r#"MATCH (`note`.`title`, `note`.`body`) AGAINST ('rust orm' IN NATURAL LANGUAGE MODE)"#
+    );
+}
+
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+#[test]
+fn falls_back_to_like_on_sqlite() {
+    use sea_orm::{QueryTrait, Value};
+
+    // Checked unrendered: inlining the values mangles the `'\'` escape literal.
+    let stmt = note::NoteSelect::new()
+        .search("50%")
+        .build()
+        .build(DbBackend::Sqlite);
+    assert!(stmt.sql.ends_with(
+        r#" WHERE LOWER("note"."title") LIKE LOWER(?) ESCAPE '\' OR LOWER("note"."body") LIKE LOWER(?) ESCAPE '\'"#
+    ));
+    let pattern = Value::from(r"%50\%%".to_owned());
+    assert_eq!(
+        stmt.values.map(|values| values.0),
+        Some(vec![pattern.clone(), pattern])
+    );
+}
@@ -0,0 +1,47 @@
+mod account {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "account")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(ieq)), delete(where(ieq)))]
+        pub email: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+
+#[test]
+fn renders_the_same_on_every_backend() {
+    let sql = |backend| {
+        account::AccountDelete::new()
+            .email_ieq("Ann@Example.com")
+            .build()
+            .unwrap()
+            .build(backend)
+            .to_string()
+    };
+    let expected =
+        r#"DELETE FROM "account" WHERE LOWER("account"."email") = LOWER('Ann@Example.com')"#;
+    assert_eq!(sql(DbBackend::Postgres), expected);
+    assert_eq!(sql(DbBackend::Sqlite), expected);
+    assert_eq!(sql(DbBackend::MySql), expected.replace('"', "`"));
+}
+
+#[test]
+fn params_keep_the_input() {
+    let (_, params) = account::AccountSelect::new()
+        .email_ieq("Ann@Example.com")
+        .build_with_params();
+    assert!(params.is_email_ieq());
+    assert_eq!(params.get_email_ieq(), Some(&"Ann@Example.com".to_string()));
+    assert_eq!(params.where_params()[0].op, "ieq");
+}
@@ -0,0 +1,90 @@
+//! Needs a backend feature: `cargo test --features postgres` (or `mysql`, `sqlite`).
+#![cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+
+mod common;
+
+mod event {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "event")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(
+            select(where(json_path(source: String = [eq, in], priority: i32 = [gt, between]))),
+            delete(where(json_path(r#type: Option<String> = [is_null])))
+        )]
+        pub metadata: Json,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::DbBackend;
+use sea_orm_builder::{WhereParam, WhereValue};
+
+#[test]
+fn typed_methods_per_key() {
+    let (_, params) = event::EventSelect::new()
+        .metadata_source_eq("api")
+        .metadata_source_in(["api", "cli"])
+        .metadata_priority_gt(3)
+        .metadata_priority_between(1, 5)
+        .build_with_params();
+    assert_eq!(params.get_metadata_source_eq(), Some(&"api".to_string()));
+    assert_eq!(params.get_metadata_priority_gt(), Some(&3));
+    assert_eq!(params.get_metadata_priority_between(), Some((&1, &5)));
+    assert_eq!(
+        params.where_params()[0],
+        WhereParam {
+            field: "metadata.source",
+            op: "eq",
+            value: WhereValue::Single(r#"Some("api")"#.into())
+        }
+    );
+
+    let stmt = event::EventDelete::new().metadata_type_is_null().build();
+    assert!(stmt.is_ok());
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn renders_arrow_operators_on_postgres() {
+    let select = event::EventSelect::new()
+        .metadata_source_eq("api")
+        .metadata_priority_gt(3);
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::Postgres),
+        r#"("event"."metadata" ->> 'source') = 'api' AND (CAST("event"."metadata" ->> 'priority' AS numeric)) > 3"#
+    );
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+#[test]
+fn renders_json_extract_on_mysql() {
+    let select = event::EventSelect::new()
+        .metadata_source_eq("api")
+        .metadata_priority_gt(3);
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::MySql),
+        r#"(JSON_UNQUOTE(JSON_EXTRACT(`event`.`metadata`, '$.source'))) = 'api' AND (JSON_EXTRACT(`event`.`metadata`, '$.priority')) > 3"#
+    );
+}
+
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+#[test]
+fn renders_json_extract_on_sqlite() {
+    let select = event::EventSelect::new()
+        .metadata_source_eq("api")
+        .metadata_priority_gt(3);
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::Sqlite),
+        r#"(json_extract("event"."metadata", '$.source')) = 'api' AND (json_extract("event"."metadata", '$.priority')) > 3"#
+    );
+}
@@ -0,0 +1,50 @@
+mod invoices {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(
+        Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder, DeleteBuilder,
+    )]
+    #[sea_orm(table_name = "invoices")]
+    #[sea_builder(config = "tests/fixtures/sea_builder.toml")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        // attributes still apply on top of the manifest
+        #[sea_builder(select(where(like)))]
+        pub status: String,
+
+        pub r#type: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+
+#[test]
+fn manifest_grants_permissions() {
+    let (stmt, params) = invoices::InvoicesSelect::new()
+        .id_in([1, 2])
+        .status_eq("open")
+        .status_like("o%")
+        .type_ne("credit")
+        .build_with_params();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "invoices"."id", "invoices"."status", "invoices"."type" FROM "invoices" WHERE "invoices"."id" IN (1, 2) AND "invoices"."status" = 'open' AND "invoices"."status" LIKE 'o%' AND "invoices"."type" <> 'credit'"#
+    );
+    assert!(params.is_type_ne());
+
+    let update = invoices::InvoicesUpdate::new()
+        .set_status("paid")
+        .status_eq("open")
+        .build();
+    assert!(update.is_ok());
+
+    let delete = invoices::InvoicesDelete::new().id_eq(1).build();
+    assert!(delete.is_ok());
+}
@@ -0,0 +1,58 @@
+mod booking {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "booking")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(not_between)), delete(where(notbetween)))]
+        pub id: i64,
+
+        #[sea_builder(select(where(not_like, not_ilike)))]
+        pub guest: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+use sea_orm_builder::WhereValue;
+
+#[test]
+fn negated_ops_render_and_record() {
+    let (stmt, params) = booking::BookingSelect::new()
+        .id_not_between(10, 20)
+        .guest_not_like("bot%")
+        .guest_not_ilike("test%")
+        .build_with_params();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "booking"."id", "booking"."guest" FROM "booking" WHERE ("booking"."id" NOT BETWEEN 10 AND 20) AND "booking"."guest" NOT LIKE 'bot%' AND ("booking"."guest" NOT ILIKE 'test%')"#
+    );
+    assert!(params.is_id_not_between());
+    assert_eq!(params.get_id_not_between(), Some((&10, &20)));
+    assert_eq!(params.get_guest_not_like(), Some(&"bot%".to_string()));
+    assert_eq!(params.get_guest_not_ilike(), Some(&"test%".to_string()));
+
+    let ops: Vec<_> = params.where_params().iter().map(|p| p.op).collect();
+    assert_eq!(ops, ["not_between", "not_like", "not_ilike"]);
+    assert_eq!(
+        params.where_params()[0].value,
+        WhereValue::Range {
+            start: "10".into(),
+            end: "20".into()
+        }
+    );
+
+    let stmt = booking::BookingDelete::new()
+        .id_not_between(1, 2)
+        .build()
+        .unwrap();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"DELETE FROM "booking" WHERE "booking"."id" NOT BETWEEN 1 AND 2"#
+    );
+}
@@ -0,0 +1,54 @@
+mod parcel {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "parcel")]
+    // only nullable fields pick up the default
+    #[sea_builder(select(where(is_not_null)))]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(eq)))]
+        pub id: i64,
+
+        #[sea_builder(select(where(is_null)), delete(where(isnull)))]
+        pub shipped_at: Option<DateTime>,
+
+        pub note: Option<String>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+use sea_orm_builder::{WhereParam, WhereValue};
+
+#[test]
+fn null_ops_take_no_arguments() {
+    let (stmt, params) = parcel::ParcelSelect::new()
+        .shipped_at_is_null()
+        .note_is_not_null()
+        .build_with_params();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "parcel"."id", "parcel"."shipped_at", "parcel"."note" FROM "parcel" WHERE "parcel"."shipped_at" IS NULL AND "parcel"."note" IS NOT NULL"#
+    );
+    assert!(params.is_shipped_at_is_null());
+    assert!(!params.is_shipped_at_is_not_null());
+    assert_eq!(
+        params.where_params()[0],
+        WhereParam {
+            field: "shipped_at",
+            op: "is_null",
+            value: WhereValue::None
+        }
+    );
+
+    // a null check alone is enough of a WHERE for deletes
+    assert!(parcel::ParcelDelete::new()
+        .shipped_at_is_null()
+        .build()
+        .is_ok());
+}
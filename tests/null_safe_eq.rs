@@ -0,0 +1,111 @@
+mod common;
+
+mod shipment {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder)]
+    #[sea_orm(table_name = "shipment")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(eq_null_safe)), update(where(is_not_distinct_from), set))]
+        pub carrier: Option<String>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::DbBackend;
+use sea_orm_builder::{WhereParam, WhereValue};
+
+fn carrier_sql(carrier: Option<String>, backend: DbBackend) -> String {
+    let stmt = shipment::ShipmentSelect::new()
+        .carrier_eq_null_safe(carrier)
+        .build();
+    where_sql(&stmt, backend)
+}
+
+#[test]
+fn takes_a_value_or_none() {
+    let (_, params) = shipment::ShipmentSelect::new()
+        .carrier_eq_null_safe("dhl")
+        .build_with_params();
+    assert!(params.is_carrier_eq_null_safe());
+    assert_eq!(
+        params.get_carrier_eq_null_safe(),
+        Some(&Some("dhl".to_string()))
+    );
+    assert_eq!(
+        params.where_params()[0],
+        WhereParam {
+            field: "carrier",
+            op: "eq_null_safe",
+            value: WhereValue::Single(r#"Some(Some("dhl"))"#.into())
+        }
+    );
+
+    let (_, params) = shipment::ShipmentSelect::new()
+        .carrier_eq_null_safe(None)
+        .build_with_params();
+    assert_eq!(params.get_carrier_eq_null_safe(), Some(&None));
+
+    // the alias generates the same method
+    let stmt = shipment::ShipmentUpdate::new()
+        .set_carrier("ups")
+        .carrier_eq_null_safe(None)
+        .build();
+    assert!(stmt.is_ok());
+}
+
+#[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+#[test]
+fn renders_portably_without_a_backend() {
+    assert_eq!(
+        carrier_sql(Some("dhl".into()), DbBackend::Postgres),
+        r#""shipment"."carrier" = 'dhl'"#
+    );
+    assert_eq!(
+        carrier_sql(None, DbBackend::Postgres),
+        r#""shipment"."carrier" IS NULL"#
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn renders_is_not_distinct_from() {
+    assert_eq!(
+        carrier_sql(Some("dhl".into()), DbBackend::Postgres),
+        r#""shipment"."carrier" IS NOT DISTINCT FROM 'dhl'"#
+    );
+    assert_eq!(
+        carrier_sql(None, DbBackend::Postgres),
+        r#""shipment"."carrier" IS NOT DISTINCT FROM NULL"#
+    );
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+#[test]
+fn renders_spaceship() {
+    assert_eq!(
+        carrier_sql(Some("dhl".into()), DbBackend::MySql),
+        r#"`shipment`.`carrier` <=> 'dhl'"#
+    );
+}
+
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+#[test]
+fn renders_is() {
+    assert_eq!(
+        carrier_sql(Some("dhl".into()), DbBackend::Sqlite),
+        r#""shipment"."carrier" IS 'dhl'"#
+    );
+    assert_eq!(
+        carrier_sql(None, DbBackend::Sqlite),
+        r#""shipment"."carrier" IS NULL"#
+    );
+}
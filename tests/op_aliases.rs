@@ -0,0 +1,47 @@
+mod customer {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "customer")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        #[sea_builder(select(where(isin, notin, neq)))]
+        pub id: i32,
+
+        #[sea_builder(select(where(ge, le)))]
+        pub age: i32,
+
+        #[sea_builder(select(where(ilike)))]
+        pub name: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+
+#[test]
+fn aliases_generate_canonical_methods() {
+    let (stmt, params) = customer::CustomerSelect::new()
+        .id_in([1, 2])
+        .id_not_in([3])
+        .id_ne(4)
+        .age_gte(18)
+        .age_lte(65)
+        .name_ilike("ann%")
+        .build_with_params();
+    assert_eq!(params.get_id_in().unwrap(), &[1, 2]);
+    assert_eq!(params.get_id_not_in().unwrap(), &[3]);
+    assert_eq!(params.get_id_ne(), Some(&4));
+    assert_eq!(params.get_age_gte(), Some(&18));
+    assert_eq!(params.get_age_lte(), Some(&65));
+    let ops: Vec<_> = params.where_params().iter().map(|p| p.op).collect();
+    assert_eq!(ops, ["in", "not_in", "ne", "gte", "lte", "ilike"]);
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "customer"."id", "customer"."age", "customer"."name" FROM "customer" WHERE "customer"."id" IN (1, 2) AND "customer"."id" NOT IN (3) AND "customer"."id" <> 4 AND "customer"."age" >= 18 AND "customer"."age" <= 65 AND ("customer"."name" ILIKE 'ann%')"#
+    );
+}
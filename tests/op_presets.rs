@@ -0,0 +1,62 @@
+mod shipment {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "shipment")]
+    // presets can be used before they are declared
+    #[sea_builder(delete(where(audit)), preset(audit = [eq, between]))]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(comparable)))]
+        pub id: i64,
+
+        #[sea_builder(override, select(where(text)))]
+        pub carrier: String,
+
+        // overlapping ops from presets are merged
+        #[sea_builder(override, select(where(membership, comparable)))]
+        pub weight: i32,
+
+        #[sea_builder(override, select(where(audit, in)))]
+        pub shipped_at: DateTime,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[test]
+fn presets_expand_to_their_ops() {
+    let ts = sea_orm::prelude::DateTime::default();
+    let (_stmt, params) = shipment::ShipmentSelect::new()
+        .id_eq(1)
+        .id_ne(2)
+        .id_lt(10)
+        .id_lte(10)
+        .id_gt(0)
+        .id_gte(0)
+        .id_between(0, 10)
+        .id_in([1])
+        .id_not_in([3])
+        .carrier_eq("ups")
+        .carrier_ne("dhl")
+        .carrier_like("u%")
+        .carrier_in(["ups"])
+        .carrier_not_in(["fedex"])
+        .weight_in([1, 2])
+        .weight_between(1, 5)
+        .shipped_at_eq(ts)
+        .shipped_at_between(ts, ts)
+        .shipped_at_in([ts])
+        .build_with_params();
+    assert_eq!(params.where_params().len(), 19);
+
+    // struct-level custom preset used as a default
+    let delete = shipment::ShipmentDelete::new()
+        .id_between(1, 2)
+        .id_eq(1)
+        .build();
+    assert!(delete.is_ok());
+}
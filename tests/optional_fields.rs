@@ -0,0 +1,72 @@
+mod member {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder)]
+    #[sea_orm(table_name = "member")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(eq)), update(where(eq)))]
+        pub id: i64,
+
+        #[sea_builder(select(where(eq, like, in)), update(set))]
+        pub nickname: Option<String>,
+
+        #[sea_builder(select(where(between, is_null)), update(set))]
+        pub age: Option<i32>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+
+#[test]
+fn filters_take_the_inner_type() {
+    let (stmt, params) = member::MemberSelect::new()
+        .nickname_eq("neo")
+        .nickname_in(["a", "b"])
+        .age_between(18, 30)
+        .build_with_params();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "member"."id", "member"."nickname", "member"."age" FROM "member" WHERE "member"."nickname" = 'neo' AND "member"."nickname" IN ('a', 'b') AND ("member"."age" BETWEEN 18 AND 30)"#
+    );
+    let nickname: Option<&String> = params.get_nickname_eq();
+    assert_eq!(nickname, Some(&"neo".to_string()));
+    assert_eq!(
+        params.get_nickname_in(),
+        Some(&["a".to_string(), "b".to_string()][..])
+    );
+    assert_eq!(params.get_age_between(), Some((&18, &30)));
+}
+
+#[test]
+fn setters_take_values_none_or_null() {
+    let update = |b: member::MemberUpdate| {
+        b.id_eq(1)
+            .build()
+            .unwrap()
+            .build(DbBackend::Postgres)
+            .to_string()
+    };
+    assert_eq!(
+        update(member::MemberUpdate::new().set_nickname("neo").set_age(42)),
+        r#"UPDATE "member" SET "nickname" = 'neo', "age" = 42 WHERE "member"."id" = 1"#
+    );
+    assert_eq!(
+        update(
+            member::MemberUpdate::new()
+                .set_nickname(None)
+                .set_age(Some(42))
+        ),
+        r#"UPDATE "member" SET "nickname" = NULL, "age" = 42 WHERE "member"."id" = 1"#
+    );
+    // `set_<field>_null` counts as a SET
+    assert_eq!(
+        update(member::MemberUpdate::new().set_nickname_null()),
+        r#"UPDATE "member" SET "nickname" = NULL WHERE "member"."id" = 1"#
+    );
+}
@@ -0,0 +1,56 @@
+mod ledger {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder)]
+    #[sea_orm(table_name = "ledger")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(eq)), update(where(eq)))]
+        pub id: i64,
+
+        #[sea_builder(select(where(eq, in)), update(set))]
+        pub r#type: String,
+
+        #[sea_builder(rename = "reference", select(where(like)))]
+        pub r#ref: String,
+
+        // `enum_name` renames the variant (and with it the column)
+        #[sea_orm(enum_name = "Amount")]
+        #[sea_builder(select(where(gte)))]
+        pub amount_cents: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use sea_orm::{DbBackend, QueryTrait};
+
+#[test]
+fn raw_identifiers_strip_for_methods_and_columns() {
+    let (stmt, params) = ledger::LedgerSelect::new()
+        .type_eq("debit")
+        .reference_like("INV-%")
+        .amount_cents_gte(100)
+        .build_with_params();
+    assert_eq!(
+        stmt.build(DbBackend::Postgres).to_string(),
+        r#"SELECT "ledger"."id", "ledger"."type", "ledger"."ref", "ledger"."amount" FROM "ledger" WHERE "ledger"."type" = 'debit' AND "ledger"."ref" LIKE 'INV-%' AND "ledger"."amount" >= 100"#
+    );
+    assert_eq!(params.get_type_eq().map(String::as_str), Some("debit"));
+    assert!(params.is_reference_like());
+    assert_eq!(params.where_params()[0].field, "type");
+    assert_eq!(params.where_params()[1].field, "ref");
+
+    let update = ledger::LedgerUpdate::new()
+        .set_type("credit")
+        .id_eq(1)
+        .build()
+        .unwrap();
+    assert_eq!(
+        update.build(DbBackend::Postgres).to_string(),
+        r#"UPDATE "ledger" SET "type" = 'credit' WHERE "ledger"."id" = 1"#
+    );
+}
@@ -0,0 +1,71 @@
+//! Needs a backend feature: `cargo test --features postgres` (or `mysql`, `sqlite`).
+#![cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+
+mod common;
+
+mod device {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "device")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(regex, not_regex)))]
+        pub serial: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::DbBackend;
+
+#[test]
+fn records_the_pattern() {
+    let (_, params) = device::DeviceSelect::new()
+        .serial_regex("^SN-[0-9]+$")
+        .build_with_params();
+    assert_eq!(params.get_serial_regex(), Some(&"^SN-[0-9]+$".to_string()));
+    assert_eq!(params.where_params()[0].op, "regex");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn renders_tilde_on_postgres() {
+    let select = device::DeviceSelect::new()
+        .serial_regex("^SN")
+        .serial_not_regex("X$");
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::Postgres),
+        r#"("device"."serial" ~ '^SN') AND ("device"."serial" !~ 'X$')"#
+    );
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+#[test]
+fn renders_regexp_on_mysql() {
+    let select = device::DeviceSelect::new()
+        .serial_regex("^SN")
+        .serial_not_regex("X$");
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::MySql),
+        r#"(`device`.`serial` REGEXP '^SN') AND (`device`.`serial` NOT REGEXP 'X$')"#
+    );
+}
+
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+#[test]
+fn renders_regexp_on_sqlite() {
+    let select = device::DeviceSelect::new()
+        .serial_regex("^SN")
+        .serial_not_regex("X$");
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::Sqlite),
+        r#"("device"."serial" REGEXP '^SN') AND ("device"."serial" NOT REGEXP 'X$')"#
+    );
+}
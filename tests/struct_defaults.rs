@@ -0,0 +1,57 @@
+mod audit_log {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "audit_log")]
+    #[sea_builder(select(where(eq, in)), delete(where(eq)))]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(between)))]
+        pub id: i64,
+
+        pub actor: String,
+
+        #[sea_builder(override, select(where(like)))]
+        pub message: String,
+
+        #[sea_builder(skip)]
+        pub payload: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[test]
+fn struct_level_defaults_apply_to_every_field() {
+    // defaults + field extension
+    let (_stmt, params) = audit_log::AuditLogSelect::new()
+        .id_eq(1)
+        .id_in([1, 2])
+        .id_between(1, 10)
+        .actor_eq("alice")
+        .actor_in(["alice", "bob"])
+        .message_like("%boot%")
+        .build_with_params();
+    assert!(params.is_id_between());
+    assert_eq!(
+        params.get_actor_in().unwrap(),
+        &["alice".to_string(), "bob".to_string()]
+    );
+    assert_eq!(
+        params.get_message_like().map(String::as_str),
+        Some("%boot%")
+    );
+    assert_eq!(params.where_params().len(), 6);
+
+    // `override` replaces the defaults, so `message` only gets the select op it names
+    let del = audit_log::AuditLogDelete::new()
+        .actor_eq("alice")
+        .id_eq(3)
+        .build_with_params();
+    let (_, del_params) = del.unwrap();
+    assert!(del_params.is_actor_eq());
+    assert!(del_params.is_id_eq());
+}
@@ -0,0 +1,134 @@
+mod common;
+
+mod customer {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "customer")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(eq)))]
+        pub region: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod order {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "order")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(in_select)), delete(where(not_in_select)))]
+        pub customer_id: i64,
+
+        #[sea_builder(select(where(in_select)))]
+        pub referrer_id: Option<i64>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+// Composite primary key, selected as a whole.
+mod membership {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "membership")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        #[sea_builder(select(where(eq)))]
+        pub tenant_id: i64,
+
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub user_id: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::{DbBackend, EntityTrait, QuerySelect, QueryTrait};
+use sea_orm_builder::{WhereParam, WhereValue};
+
+#[test]
+fn in_select_nests_a_builder() {
+    let (stmt, params) = order::OrderSelect::new()
+        .customer_id_in_select(customer::CustomerSelect::new().region_eq("eu"))
+        .build_with_params();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Sqlite),
+        r#""order"."customer_id" IN (SELECT "customer"."id" FROM "customer" WHERE "customer"."region" = 'eu')"#
+    );
+
+    let nested = vec![WhereParam {
+        field: "region",
+        op: "eq",
+        value: WhereValue::Single(r#"Some("eu")"#.into()),
+    }];
+    assert_eq!(params.get_customer_id_in_select(), Some(&nested[..]));
+    assert_eq!(
+        params.where_params()[0],
+        WhereParam {
+            field: "customer_id",
+            op: "in_select",
+            value: WhereValue::Nested(nested)
+        }
+    );
+}
+
+#[test]
+fn in_select_takes_a_projected_select() {
+    let select = customer::Entity::find()
+        .select_only()
+        .column(customer::Column::Id);
+    let (stmt, params) = order::OrderSelect::new()
+        .referrer_id_in_select(select)
+        .build_with_params();
+    assert!(stmt
+        .build(DbBackend::Sqlite)
+        .to_string()
+        .ends_with(r#"WHERE "order"."referrer_id" IN (SELECT "customer"."id" FROM "customer")"#));
+    assert_eq!(params.get_referrer_id_in_select(), Some(&[][..]));
+}
+
+#[test]
+fn not_in_select_on_delete() {
+    let stmt = order::OrderDelete::new()
+        .customer_id_not_in_select(customer::CustomerSelect::new())
+        .build()
+        .unwrap();
+    assert_eq!(
+        stmt.build(DbBackend::Sqlite).to_string(),
+        r#"DELETE FROM "order" WHERE "order"."customer_id" NOT IN (SELECT "customer"."id" FROM "customer")"#
+    );
+}
+
+#[test]
+fn composite_key_selects_every_key_column() {
+    use sea_orm::sea_query::SqliteQueryBuilder;
+    use sea_orm_builder::IntoSubquery;
+
+    let (query, _) = IntoSubquery::<(i64, i64)>::into_subquery(
+        membership::MembershipSelect::new().tenant_id_eq(1),
+    );
+    assert_eq!(
+        query.to_string(SqliteQueryBuilder),
+        r#"SELECT "membership"."tenant_id", "membership"."user_id" FROM "membership" WHERE "membership"."tenant_id" = 1"#
+    );
+}
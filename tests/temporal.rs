@@ -0,0 +1,139 @@
+mod common;
+
+mod event {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+    #[sea_orm(table_name = "event")]
+    #[sea_builder(select(where(eq, temporal)))]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(delete(where(before)))]
+        pub created_at: DateTimeUtc,
+
+        pub day: Date,
+
+        pub shipped_at: Option<DateTimeUtc>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use std::time::Duration;
+
+use common::where_sql;
+use sea_orm::prelude::{Date, DateTimeUtc};
+use sea_orm::{DbBackend, QueryTrait};
+use sea_orm_builder::{FixedClock, WhereValue};
+
+fn date(s: &str) -> Date {
+    s.parse().unwrap()
+}
+
+fn ts(s: &str) -> DateTimeUtc {
+    s.parse().unwrap()
+}
+
+#[test]
+fn on_matches_the_whole_day() {
+    let select = event::EventSelect::new().created_at_on(date("2024-02-29"));
+    assert_eq!(
+        select.where_params()[0].value,
+        WhereValue::Range {
+            start: "2024-02-29T00:00:00Z".into(),
+            end: "2024-03-01T00:00:00Z".into()
+        }
+    );
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::Sqlite),
+        r#""event"."created_at" >= '2024-02-29 00:00:00.000000 +00:00' AND "event"."created_at" < '2024-03-01 00:00:00.000000 +00:00'"#
+    );
+    assert_eq!(
+        where_sql(
+            &event::EventSelect::new().day_on(date("2024-12-31")).build(),
+            DbBackend::Sqlite
+        ),
+        r#""event"."day" >= '2024-12-31' AND "event"."day" < '2025-01-01'"#
+    );
+}
+
+#[test]
+fn before_and_since_are_half_open() {
+    let (stmt, params) = event::EventSelect::new()
+        .created_at_since(ts("2024-01-01T00:00:00Z"))
+        .shipped_at_before(ts("2024-02-01T00:00:00Z"))
+        .build_with_params();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Sqlite),
+        r#""event"."created_at" >= '2024-01-01 00:00:00.000000 +00:00' AND "event"."shipped_at" < '2024-02-01 00:00:00.000000 +00:00'"#
+    );
+    assert_eq!(
+        params.get_shipped_at_before(),
+        Some(&ts("2024-02-01T00:00:00Z"))
+    );
+}
+
+#[test]
+fn within_last_uses_the_clock() {
+    let clock = FixedClock(ts("2024-03-10T12:00:00Z"));
+    let (stmt, params) = event::EventSelect::new()
+        .with_clock(clock)
+        .created_at_within_last(Duration::from_secs(2 * 60 * 60))
+        .day_within_last(Duration::from_secs(7 * 24 * 60 * 60))
+        .build_with_params();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Sqlite),
+        r#"("event"."created_at" BETWEEN '2024-03-10 10:00:00.000000 +00:00' AND '2024-03-10 12:00:00.000000 +00:00') AND ("event"."day" BETWEEN '2024-03-03' AND '2024-03-10')"#
+    );
+    assert_eq!(
+        params.get_day_within_last(),
+        Some(&Duration::from_secs(7 * 24 * 60 * 60))
+    );
+    assert_eq!(
+        params.where_params()[0].value,
+        WhereValue::Range {
+            start: "2024-03-10T10:00:00Z".into(),
+            end: "2024-03-10T12:00:00Z".into()
+        }
+    );
+}
+
+#[test]
+fn out_of_range_inputs_clamp() {
+    let select = event::EventSelect::new()
+        .with_clock(FixedClock(ts("2024-03-10T12:00:00Z")))
+        .created_at_within_last(Duration::from_secs(u64::MAX))
+        .created_at_on(Date::MAX)
+        .day_on(Date::MAX);
+    assert_eq!(
+        select.where_params()[0].value,
+        WhereValue::Range {
+            start: format!("{:?}", DateTimeUtc::MIN_UTC),
+            end: "2024-03-10T12:00:00Z".into()
+        }
+    );
+    assert_eq!(
+        select.where_params()[1].value,
+        WhereValue::Range {
+            start: format!("{:?}", Date::MAX.and_time(Default::default()).and_utc()),
+            end: format!("{:?}", DateTimeUtc::MAX_UTC)
+        }
+    );
+}
+
+#[test]
+fn single_ops_work_on_other_builders() {
+    let stmt = event::EventDelete::new()
+        .created_at_before(ts("2023-01-01T00:00:00Z"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        stmt.build(DbBackend::Sqlite).to_string(),
+        r#"DELETE FROM "event" WHERE "event"."created_at" < '2023-01-01 00:00:00.000000 +00:00'"#
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+mod article {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "article")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i64,
+
+        #[sea_builder(select(where(starts_with, ends_with, contains)))]
+        pub title: String,
+
+        #[sea_builder(select(where(istartswith, iends_with, icontains)))]
+        pub author: Option<String>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::DbBackend;
+use sea_orm_builder::{escape_like, WhereValue};
+
+#[test]
+fn wildcards_in_the_input_match_literally() {
+    assert_eq!(escape_like(r"50%_off\now"), r"50\%\_off\\now");
+
+    let select = article::ArticleSelect::new()
+        .title_starts_with("50%")
+        .title_ends_with("a_b")
+        .title_contains("x");
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::Postgres),
+        r#""article"."title" LIKE E'50\\%%' ESCAPE E'\\' AND "article"."title" LIKE E'%a\\_b' ESCAPE E'\\' AND "article"."title" LIKE '%x%' ESCAPE E'\\'"#
+    );
+}
+
+#[test]
+fn case_insensitive_variants_lower_both_sides() {
+    let select = article::ArticleSelect::new()
+        .author_icontains("Ann")
+        .author_istarts_with("a")
+        .author_iends_with("z");
+    assert_eq!(
+        where_sql(&select.build(), DbBackend::Postgres),
+        r#"LOWER("article"."author") LIKE LOWER('%Ann%') ESCAPE E'\\' AND LOWER("article"."author") LIKE LOWER('a%') ESCAPE E'\\' AND LOWER("article"."author") LIKE LOWER('%z') ESCAPE E'\\'"#
+    );
+}
+
+#[test]
+fn params_keep_the_raw_input() {
+    let (_, params) = article::ArticleSelect::new()
+        .title_contains("100%")
+        .build_with_params();
+    assert_eq!(params.get_title_contains(), Some(&"100%".to_string()));
+    assert_eq!(
+        params.where_params()[0].value,
+        WhereValue::Single(r#"Some("100%")"#.into())
+    );
+}
@@ -0,0 +1,98 @@
+mod common;
+
+mod membership {
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(
+        Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, UpdateBuilder, DeleteBuilder,
+    )]
+    #[sea_orm(table_name = "membership")]
+    #[sea_builder(
+        select(where(tuple_in(tenant_id, user_id))),
+        update(where(tuple_in(tenant_id, user_id))),
+        delete(where(tuple_in(tenant_id, role)))
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub tenant_id: i64,
+
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub user_id: i64,
+
+        #[sea_builder(update(set))]
+        pub role: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+use common::where_sql;
+use sea_orm::DbBackend;
+
+#[cfg(not(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql")))))]
+#[test]
+fn select_matches_pairs() {
+    use sea_orm_builder::{WhereParam, WhereValue};
+
+    let (stmt, params) = membership::MembershipSelect::new()
+        .tenant_id_user_id_in([(1, 2), (3, 4)])
+        .build_with_params();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Postgres),
+        r#"("membership"."tenant_id", "membership"."user_id") IN ((1, 2), (3, 4))"#
+    );
+    let pairs: Option<&[(i64, i64)]> = params.get_tenant_id_user_id_in();
+    assert_eq!(pairs, Some(&[(1, 2), (3, 4)][..]));
+    assert_eq!(
+        params.where_params()[0],
+        WhereParam {
+            field: "tenant_id,user_id",
+            op: "in",
+            value: WhereValue::List(vec!["(1, 2)".into(), "(3, 4)".into()])
+        }
+    );
+}
+
+#[cfg(not(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql")))))]
+#[test]
+fn update_and_delete_take_tuples() {
+    use sea_orm::QueryTrait;
+
+    let update = membership::MembershipUpdate::new()
+        .set_role("admin")
+        .tenant_id_user_id_in([(1, 2)])
+        .build()
+        .unwrap();
+    assert_eq!(
+        update.build(DbBackend::Postgres).to_string(),
+        r#"UPDATE "membership" SET "role" = 'admin' WHERE ("membership"."tenant_id", "membership"."user_id") IN ((1, 2))"#
+    );
+
+    let (delete, params) = membership::MembershipDelete::new()
+        .tenant_id_role_in([(1, "guest")])
+        .build_with_params()
+        .unwrap();
+    assert_eq!(
+        delete.build(DbBackend::Postgres).to_string(),
+        r#"DELETE FROM "membership" WHERE ("membership"."tenant_id", "membership"."role") IN ((1, 'guest'))"#
+    );
+    assert_eq!(
+        params.get_tenant_id_role_in(),
+        Some(&[(1, "guest".to_string())][..])
+    );
+}
+
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+#[test]
+fn sqlite_spells_out_the_pairs() {
+    let stmt = membership::MembershipSelect::new()
+        .tenant_id_user_id_in([(1, 2), (3, 4)])
+        .build();
+    assert_eq!(
+        where_sql(&stmt, DbBackend::Sqlite),
+        r#"("membership"."tenant_id" = 1 AND "membership"."user_id" = 2) OR ("membership"."tenant_id" = 3 AND "membership"."user_id" = 4)"#
+    );
+}
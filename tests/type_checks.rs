@@ -0,0 +1,77 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveValueType)]
+pub struct Sku(pub String);
+
+impl From<Sku> for String {
+    fn from(sku: Sku) -> String {
+        sku.0
+    }
+}
+
+impl sea_orm_builder::kind::Textual for Sku {}
+
+#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(1))")]
+pub enum Tier {
+    #[sea_orm(string_value = "g")]
+    Gold,
+    #[sea_orm(string_value = "s")]
+    Silver,
+}
+
+mod product {
+    use super::{Sku, Tier};
+    use sea_orm::entity::prelude::*;
+    use sea_orm_builder::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+    #[sea_orm(table_name = "product")]
+    #[sea_builder(select(where(eq, gt, like)))]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+
+        pub title: String,
+
+        // `gt` and `like` from the defaults don't apply to a bool
+        pub active: bool,
+
+        #[sea_builder(select(where(like)))]
+        pub sku: Sku,
+
+        // stored as an integer but only ever compared for equality
+        #[sea_builder(kind = "enum")]
+        pub status: i16,
+
+        // an active enum looks like any other type, so it is marked
+        #[sea_builder(kind = "enum", select(where(in)))]
+        pub tier: Tier,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[test]
+fn default_ops_follow_the_field_type() {
+    let (_stmt, params) = product::ProductSelect::new()
+        .id_eq(1)
+        .id_gt(0)
+        .title_like("%lamp%")
+        .title_gt("a")
+        .active_eq(true)
+        .sku_eq(Sku("A-1".into()))
+        .sku_like(Sku("A-%".into()))
+        .status_eq(2)
+        .tier_eq(Tier::Gold)
+        .tier_in([Tier::Gold, Tier::Silver])
+        .build_with_params();
+    assert!(params.is_title_like());
+    assert!(params.is_active_eq());
+    assert_eq!(params.get_sku_like(), Some(&Sku("A-%".into())));
+    assert_eq!(params.get_status_eq(), Some(&2));
+    assert_eq!(params.get_tier_in(), Some(&[Tier::Gold, Tier::Silver][..]));
+    assert_eq!(params.where_params().len(), 10);
+}
@@ -0,0 +1,23 @@
+mod tickets {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "tickets")]
+    pub struct Model {
+        #[sea_orm(primary_key, enum_name = "TicketId")]
+        pub id: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+// `id` is `Column::TicketId`, so it needs `id: i64 as TicketId`
+sea_orm_builder::builders!(
+    tickets,
+    fields { id: i64 },
+    select { id: [eq] },
+);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+#[sea_orm(table_name = "account")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    #[sea_builder(select(where(cmp_col(lt))))]
+    pub balance: Decimal,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {}
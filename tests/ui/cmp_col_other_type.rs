@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+#[sea_orm(table_name = "account")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    #[sea_builder(select(where(cmp_col(lt))))]
+    pub balance: Decimal,
+    pub credit_limit: Decimal,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {
+    // a column of another type isn't among the partners
+    let _ = AccountSelect::new().balance_lt_col(AccountSelectBalanceCol::UpdatedAt);
+}
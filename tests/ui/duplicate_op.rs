@@ -0,0 +1,16 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+#[sea_orm(table_name = "items")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[sea_builder(select(where(eq, eq)))]
+    pub id: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+// `config` is relative to the project trybuild generates in target/tests/trybuild.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+#[sea_orm(table_name = "manifest_type_mismatch")]
+#[sea_builder(config = "../../../../tests/ui/manifests/sea_builder.toml")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub status: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {}
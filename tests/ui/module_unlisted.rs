@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder, DeleteBuilder)]
+#[sea_orm(table_name = "items")]
+#[sea_builder(module(name = "builders", select))]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[sea_builder(select(where(eq)), delete(where(eq)))]
+    pub id: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+// A newtype can't be classified, so `like` falls back to a trait bound.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveValueType)]
+pub struct Sku(pub String);
+
+impl From<Sku> for String {
+    fn from(sku: Sku) -> String {
+        sku.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+#[sea_orm(table_name = "items")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    #[sea_builder(select(where(like)))]
+    pub sku: Sku,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+// `override` drops the struct defaults, not the type checks.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+#[sea_orm(table_name = "items")]
+#[sea_builder(select(where(eq)))]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    #[sea_builder(override, select(where(between)))]
+    pub active: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {}
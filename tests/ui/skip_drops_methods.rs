@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+use sea_orm_builder::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, SelectBuilder)]
+#[sea_orm(table_name = "audit_log")]
+#[sea_builder(select(where(eq, in)))]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    #[sea_builder(override, select(where(like)))]
+    pub message: String,
+    #[sea_builder(skip)]
+    pub payload: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+impl ActiveModelBehavior for ActiveModel {}
+
+fn main() {
+    // a skipped field gets no methods at all
+    let _ = AuditLogSelect::new().payload_eq("{}");
+}